@@ -29,6 +29,7 @@ use syn::{self, ItemFn, ReturnType, parse_macro_input, parse_quote};
 /// Instead of creating subcommands as options, you can use `subcommand_groups` and `subcommands`.\
 /// `Vec`s of values can be constructed by simply using `[]` and comma separating the values, structs and maps can be done with `{}` following the same syntax inside.\
 /// If you're creating a "fake" command (as a separate component handler for example), you can set `ignore = true` to make sure that command isn't synced.
+/// `Client::register_component_handler` and `Client::register_modal_handler` can be used instead to keep such handlers out of the commands entirely.
 /// ## Example
 /// ```ignore
 /// #[command(
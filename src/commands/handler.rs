@@ -8,11 +8,16 @@
 //! Structs used for handling commands
 
 use std::{
+  any::{Any, TypeId},
   collections::HashMap,
   sync::{Arc, Mutex},
 };
-use crate::tokio::{spawn, sync::{mpsc, oneshot}};
-use anyhow::{anyhow, bail, Context};
+use crate::tokio::{spawn, sync::{mpsc, Semaphore}};
+#[cfg(feature = "server")]
+use crate::tokio::sync::oneshot;
+use anyhow::{bail, Context};
+#[cfg(feature = "server")]
+use anyhow::anyhow;
 
 use crate::structs::{
   interactions::{
@@ -30,7 +35,7 @@ use crate::structs::{
   Snowflake,
   Permissions
 };
-use super::{Command, responder::{CommandResponder, CommandResponse}};
+use super::{AsyncCmdFn, Command, responder::{CommandResponder, CommandResponse, ResponsePolicy}};
 use crate::rest::Rest;
 
 /// Values passed as inputs for your command
@@ -111,48 +116,100 @@ pub struct CommandInput {
   pub context: Option<InteractionContextType>,
   /// Handler for Discord API calls
   pub rest: Rest,
+  pub(crate) state: Arc<StateMap>,
 }
 
+pub(crate) type StateMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
 pub(crate) struct CommandHandler {
-  pub(crate) commands: HashMap<String, Arc<Mutex<Command>>>
+  pub(crate) commands: HashMap<String, Arc<Mutex<Command>>>,
+  pub(crate) component_handlers: HashMap<String, Arc<Mutex<Command>>>,
+  pub(crate) modal_handlers: HashMap<String, Arc<Mutex<Command>>>,
+  pub(crate) state: Arc<StateMap>,
+  pub(crate) concurrency_limit: Option<Arc<Semaphore>>
 }
 
 impl CommandHandler {
   pub fn new() -> Self {
     Self {
-      commands: HashMap::new()
+      commands: HashMap::new(),
+      component_handlers: HashMap::new(),
+      modal_handlers: HashMap::new(),
+      state: Arc::new(HashMap::new()),
+      concurrency_limit: None
     }
   }
 
+  pub fn set_concurrency_limit(&mut self, limit: Option<usize>) {
+    self.concurrency_limit = limit.map(|limit| Arc::new(Semaphore::new(limit)));
+  }
+
+  pub fn manage<T: Send + Sync + 'static>(&mut self, state: T) {
+    Arc::make_mut(&mut self.state).insert(TypeId::of::<T>(), Arc::new(state));
+  }
+
   pub fn add(&mut self, command: Command) {
     self.commands.insert(command.name.clone(), Arc::new(Mutex::new(command)));
   }
 
-  pub fn convert_commands(&self) -> anyhow::Result<Vec<ApplicationCommand>> {
+  fn handler_command<F: AsyncCmdFn + 'static>(name: String, func: F) -> Arc<Mutex<Command>> {
+    Arc::new(Mutex::new(Command {
+      func: Box::new(func),
+      ignore: true,
+      name,
+      ..Default::default()
+    }))
+  }
+
+  pub fn add_component_handler<F: AsyncCmdFn + 'static>(&mut self, name: String, func: F) {
+    self.component_handlers.insert(name.clone(), Self::handler_command(name, func));
+  }
+
+  pub fn add_modal_handler<F: AsyncCmdFn + 'static>(&mut self, name: String, func: F) {
+    self.modal_handlers.insert(name.clone(), Self::handler_command(name, func));
+  }
+
+  pub fn convert_commands(&self) -> anyhow::Result<Vec<(ApplicationCommand, Option<Vec<Snowflake>>)>> {
     let mut vec = Vec::new();
+    let mut errors = Vec::new();
+
+    for (name, c) in self.commands.iter() {
+      let command = match c.lock() {
+        Ok(command) => command,
+        Err(_) => {
+          errors.push(format!("{}: Command had been poisoned", name));
+          continue;
+        }
+      };
+      if command.ignore { continue }
 
-    for c in self.commands.values() {
-      let command = &*c.lock().map_err(|_| anyhow::Error::msg("Command had been poisoned"))?;
-      if !command.ignore {
-        vec.push(command.clone().try_into()?);
+      match ApplicationCommand::from_command_ref(&command) {
+        Ok(converted) => vec.push((converted, command.guild_ids.clone())),
+        Err(err) => errors.push(format!("{}: {:#}", name, err))
       }
     }
 
+    if !errors.is_empty() {
+      errors.sort();
+      bail!("Failed to convert {} command(s):\n{}", errors.len(), errors.join("\n"));
+    }
+
     Ok(vec)
   }
 
+  #[cfg(feature = "server")]
   pub async fn rocket_bridge(self: &Arc<Self>, mut receiver: mpsc::UnboundedReceiver::<RocketCommand>) {
     while let Some(command) = receiver.recv().await {
       let command_handler = self.clone();
       spawn(async move {
-        let RocketCommand(interaction, bot_token, handler_send) = command;
+        let RocketCommand(interaction, bot_token, rest, handler_send) = command;
 
         let value = if let
         InteractionType::APPLICATION_COMMAND |
         InteractionType::MESSAGE_COMPONENT |
         InteractionType::APPLICATION_COMMAND_AUTOCOMPLETE |
         InteractionType::MODAL_SUBMIT = interaction.interaction_type {
-          command_handler.handle_command(interaction, bot_token).await
+          command_handler.handle_command(interaction, bot_token, rest).await
         } else {
           Err(anyhow!("Unexpected InteractionType in rocket_bridge"))
         };
@@ -162,17 +219,17 @@ impl CommandHandler {
     }
   }
 
-  fn parse_options(&self, options: Vec<InteractionOption>, resolved: &Option<InteractionDataResolved>, input: &mut CommandInput) -> anyhow::Result<()> {
+  fn parse_options(options: Vec<InteractionOption>, resolved: &Option<InteractionDataResolved>, input: &mut CommandInput) -> anyhow::Result<()> {
     for option in options.into_iter() {
       let option_value = match option.option_type {
         InteractionOptionType::SUB_COMMAND_GROUP => {
           input.subcommand_group = Some(option.name);
-          return self.parse_options(option.options.context("Subcommand group has no subcommands")?, resolved, input)
+          return Self::parse_options(option.options.context("Subcommand group has no subcommands")?, resolved, input)
         },
         InteractionOptionType::SUB_COMMAND => {
           input.subcommand = Some(option.name);
           if option.options.is_none() { return Ok(()) }
-          return self.parse_options(option.options.unwrap(), resolved, input)
+          return Self::parse_options(option.options.unwrap(), resolved, input)
         },
 
         InteractionOptionType::STRING => OptionValue::String(
@@ -215,7 +272,7 @@ impl CommandHandler {
           ).context("Role option provided but no matching resolved role found")?
           .clone()
         ),
-        InteractionOptionType::MENTIONABLE => self.parse_mentionable(
+        InteractionOptionType::MENTIONABLE => Self::parse_mentionable(
           resolved.as_ref().context("Mentionable option provided but no resolved object")?,
           option.value.as_ref().context("Mentionable option has no value")?.as_str().context("Mentionable option value is not a string (user or role id)")?
         )?,
@@ -242,7 +299,7 @@ impl CommandHandler {
     Ok(())
   }
 
-  fn parse_select_values(&self, values: Vec<String>, resolved: &Option<InteractionDataResolved>, input: &mut CommandInput) -> anyhow::Result<()> {
+  fn parse_select_values(values: Vec<String>, resolved: &Option<InteractionDataResolved>, input: &mut CommandInput) -> anyhow::Result<()> {
     let mut resolved_values = Vec::new();
     match input.component_type.as_ref().context("Somehow trying to parse values without a component type")? {
       ComponentType::USER_SELECT => {
@@ -268,7 +325,7 @@ impl CommandHandler {
       ComponentType::MENTIONABLE_SELECT => {
         for value in values.iter() {
           resolved_values.push(
-            self.parse_mentionable(resolved.as_ref().context("Mentionable select provided but no resolved object")?, value)?
+            Self::parse_mentionable(resolved.as_ref().context("Mentionable select provided but no resolved object")?, value)?
           )
         }
       },
@@ -289,11 +346,11 @@ impl CommandHandler {
     Ok(())
   }
 
-  fn parse_component_values(&self, components: Vec<Component>, input: &mut CommandInput) {
+  fn parse_component_values(components: Vec<Component>, input: &mut CommandInput) {
     for component in components.into_iter() {
       match component {
         Component::ActionRow(action_row) => {
-          self.parse_component_values(action_row.components, input);
+          Self::parse_component_values(action_row.components, input);
         },
         Component::TextInput(text_input) => {
           let value = OptionValue::String(text_input.value.unwrap_or_default());
@@ -304,7 +361,7 @@ impl CommandHandler {
     }
   }
 
-  fn parse_mentionable(&self, resolved: &InteractionDataResolved, option_value: &str) -> anyhow::Result<OptionValue> {
+  fn parse_mentionable(resolved: &InteractionDataResolved, option_value: &str) -> anyhow::Result<OptionValue> {
     let mut found_value = None;
     if let Some(users) = &resolved.users {
       if let Some(user) = users.get(option_value) {
@@ -323,7 +380,7 @@ impl CommandHandler {
     }
   }
 
-  fn parse_resolved(&self, resolved: Option<InteractionDataResolved>, target_id: Option<String>, input: &mut CommandInput) -> anyhow::Result<()> {
+  fn parse_resolved(resolved: Option<InteractionDataResolved>, target_id: Option<String>, input: &mut CommandInput) -> anyhow::Result<()> {
     match input.command_type.as_ref().context("Somehow trying to parse resolved without a command type")? {
       ApplicationCommandType::USER => {
         let target_id = target_id.context("User context menu command has no target")?;
@@ -334,8 +391,11 @@ impl CommandHandler {
         if let Some(mut resolved_members) = resolved.members {
           member = resolved_members.remove(&target_id);
         }
+        input.target_member = match (member, &user) {
+          (Some(member), Some(user)) => Some(member.with_user(user.clone())),
+          (member, _) => member
+        };
         input.target_user = user;
-        input.target_member = member;
       },
       ApplicationCommandType::MESSAGE => {
         let target_id = target_id.context("Message context menu command has no target")?;
@@ -351,20 +411,29 @@ impl CommandHandler {
     Ok(())
   }
 
-  fn parse_user(&self, user: Option<User>, member: &Option<GuildMember>) -> anyhow::Result<User> {
-    member.as_ref().map_or_else(|| user.context("No member or user provided"), |m| m.user.clone().context("No user object in member object"))
+  fn parse_user(user: Option<User>, member: &Option<GuildMember>) -> anyhow::Result<User> {
+    member.as_ref().and_then(|m| m.user.clone()).or(user).context("No member or user provided")
   }
 
-  async fn spawn_command(&self, command: Arc<Mutex<Command>>, id: String, token: String, input: CommandInput) -> anyhow::Result<CommandResponse> {
+  async fn spawn_command(&self, command: Arc<Mutex<Command>>, interaction_id: String, id: String, token: String, rest: Rest, input: CommandInput) -> anyhow::Result<CommandResponse> {
     let (tx, mut rx) = mpsc::unbounded_channel::<CommandResponse>();
     let responder = CommandResponder {
       tx,
+      interaction_id,
       id,
       token,
-      rest: Rest::new()
+      rest,
+      policy: ResponsePolicy::default()
+    };
+
+    // Interactions over the limit wait here for a running handler to finish
+    let permit = match &self.concurrency_limit {
+      Some(semaphore) => Some(semaphore.clone().acquire_owned().await?),
+      None => None
     };
 
     spawn(async move {
+      let _permit = permit;
       let fut = command.lock().unwrap().func.call(input, responder);
       if let Err(err) = fut.await {
         eprintln!("Error returned from command handler: {:?}", err);
@@ -374,10 +443,17 @@ impl CommandHandler {
     let response = rx.recv().await.context("Command handler finished without responding")?;
     rx.close();
 
+    // The responder already rejects invalid messages, this is a backstop for handlers that ignore that error so it still gets reported
+    match &response {
+      CommandResponse::SendMessage(message) => message.validate()?,
+      CommandResponse::UpdateMessage(message) => message.validate_limits()?,
+      _ => ()
+    }
+
     Ok(response)
   }
 
-  pub async fn handle_command(&self, interaction: Interaction, bot_token: Option<String>) -> anyhow::Result<InteractionCallback> {
+  pub(crate) fn parse_input(interaction: Interaction, rest: Rest, state: Arc<StateMap>) -> anyhow::Result<CommandInput> {
     let data = interaction.data.context("Interaction has no data")?;
 
     let (name, custom_id): (String, Option<String>) = match interaction.interaction_type {
@@ -392,9 +468,7 @@ impl CommandHandler {
       _ => bail!("Unexpected InteractionType in handle_command")
     };
 
-    let command = self.commands.get(&name).with_context(|| format!("Received command ({}) has no registered command handler", name))?;
-    let task_command = command.clone();
-
+    let user = Self::parse_user(interaction.user, &interaction.member)?;
     let mut input = CommandInput {
       interaction_type: interaction.interaction_type,
       command_type: data.command_type,
@@ -404,12 +478,12 @@ impl CommandHandler {
       subcommand_group: None,
       args: HashMap::new(),
       resolved: None,
-      application_id: interaction.application_id.clone(),
+      application_id: interaction.application_id,
       guild_id: interaction.guild_id,
       channel_id: interaction.channel_id,
       channel: interaction.channel,
-      user: self.parse_user(interaction.user, &interaction.member)?,
-      member: interaction.member,
+      member: interaction.member.map(|m| m.with_user(user.clone())),
+      user,
       message: interaction.message,
       target_user: None,
       target_member: None,
@@ -424,31 +498,202 @@ impl CommandHandler {
       entitlements: interaction.entitlements,
       authorizing_integration_owners: interaction.authorizing_integration_owners,
       context: interaction.context,
-      rest: Rest::with_optional_token(bot_token)
+      rest,
+      state
     };
 
     if let Some(options) = data.options {
-      self.parse_options(options, &data.resolved, &mut input)?;
+      Self::parse_options(options, &data.resolved, &mut input)?;
     }
 
     if let Some(components) = data.components {
-      self.parse_component_values(components, &mut input);
+      Self::parse_component_values(components, &mut input);
     }
 
     if let Some(values) = data.values {
-      self.parse_select_values(values, &data.resolved, &mut input)?;
+      Self::parse_select_values(values, &data.resolved, &mut input)?;
     }
 
     if input.command_type.is_some() {
-      self.parse_resolved(data.resolved, data.target_id, &mut input)?;
+      Self::parse_resolved(data.resolved, data.target_id, &mut input)?;
     }
 
-    let response = self.spawn_command(task_command, interaction.application_id, interaction.token, input).await?;
+    Ok(input)
+  }
+
+  pub async fn handle_command(&self, interaction: Interaction, bot_token: Option<String>, rest: Option<Rest>) -> anyhow::Result<InteractionCallback> {
+    let interaction_id = interaction.id.clone();
+    let application_id = interaction.application_id.clone();
+    let token = interaction.token.clone();
+
+    let input_rest = match &rest {
+      Some(rest) => rest.clone(),
+      None => Rest::with_optional_token(bot_token).context("Failed to create a Rest handler with the configured bot token")?
+    };
+    let input = Self::parse_input(interaction, input_rest, self.state.clone())?;
+
+    let handlers = match input.interaction_type {
+      InteractionType::MESSAGE_COMPONENT => self.component_handlers.get(&input.command),
+      InteractionType::MODAL_SUBMIT => self.modal_handlers.get(&input.command),
+      _ => None
+    };
+    let command = handlers.or_else(|| self.commands.get(&input.command)).with_context(|| format!("Received command ({}) has no registered command handler", input.command))?;
+    let task_command = command.clone();
+
+    let responder_rest = match rest {
+      Some(rest) => rest,
+      None => Rest::new()
+    };
+    let response = self.spawn_command(task_command, interaction_id, application_id, token, responder_rest, input).await?;
     Ok(response.into())
   }
 }
 
 impl CommandInput {
+  /// Parses an interaction into the input a command handler receives, using `rest` as its [Rest] handler\
+  /// This is the parsing done by the webhook listener, for when you receive and [verify](crate::verify_signature) interactions yourself.
+  /// The parsed input has no shared state. Use [`Client::dispatch`](crate::Client::dispatch) to also run the matching command.
+  /// ```
+  /// # use slashook::{commands::CommandInput, rest::Rest, structs::interactions::Interaction};
+  /// let interaction: Interaction = serde_json::from_value(serde_json::json!({
+  ///   "id": "1", "application_id": "2", "type": 2, "token": "token", "version": 1, "locale": "en-US",
+  ///   "app_permissions": "0", "entitlements": [],
+  ///   "user": { "id": "3", "username": "user", "discriminator": "0", "avatar": null },
+  ///   "data": { "id": "4", "name": "echo", "type": 1, "options": [{ "name": "text", "type": 3, "value": "Hello" }] }
+  /// })).unwrap();
+  /// let input = CommandInput::parse(interaction, Rest::new()).unwrap();
+  /// assert_eq!(input.command, "echo");
+  /// assert_eq!(input.args.get("text").unwrap().as_string().unwrap(), "Hello");
+  /// ```
+  pub fn parse(interaction: Interaction, rest: Rest) -> anyhow::Result<Self> {
+    CommandHandler::parse_input(interaction, rest, Arc::new(HashMap::new()))
+  }
+
+  /// Gets a value from the shared state added with [`Client::manage`](crate::Client::manage)
+  ///
+  /// Returns `None` if no value of that type is being managed.
+  pub fn state<T: Send + Sync + 'static>(&self) -> Option<&T> {
+    self.state.get(&TypeId::of::<T>()).and_then(|s| s.downcast_ref::<T>())
+  }
+
+  /// Returns the `/`-separated segments of the custom_id after the command name, or an empty Vec for interactions without one.
+  /// See [CustomId](crate::structs::components::CustomId) for building custom_ids with multiple segments.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// #[command(name = "search", description = "Search for something")]
+  /// fn search(input: CommandInput, res: CommandResponder) {
+  ///   // A button with the custom_id `search/page/5` was clicked
+  ///   if let ["page", page] = input.custom_id_segments()[..] {
+  ///     let page: usize = page.parse()?;
+  ///   }
+  /// }
+  /// ```
+  pub fn custom_id_segments(&self) -> Vec<&str> {
+    self.custom_id.as_deref().map(|id| id.split('/').collect()).unwrap_or_default()
+  }
+
+  /// Returns how the command was invoked, or `None` for component, modal and autocomplete interactions without a command type.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::interactions::ApplicationCommandType;
+  /// ##[command(name = "Info", description = "Shows info")]
+  /// fn info(input: CommandInput, res: CommandResponder) {
+  ///   let about = match input.command_type() {
+  ///     Some(ApplicationCommandType::USER) => format!("User {}", input.target_user.unwrap().username),
+  ///     Some(ApplicationCommandType::MESSAGE) => format!("Message {}", input.target_message.unwrap().id),
+  ///     _ => String::from("Nothing in particular")
+  ///   };
+  ///   res.send_message(about).await?;
+  /// }
+  /// ```
+  pub fn command_type(&self) -> Option<ApplicationCommandType> {
+    self.command_type.clone()
+  }
+
+  /// Returns the [context](InteractionContextType) the interaction was triggered from as sent by Discord
+  /// ```
+  /// # use slashook::{commands::CommandInput, rest::Rest};
+  /// # use slashook::structs::interactions::{Interaction, InteractionContextType};
+  /// let interaction: Interaction = serde_json::from_value(serde_json::json!({
+  ///   "id": "1", "application_id": "2", "type": 2, "token": "token", "version": 1, "locale": "en-US",
+  ///   "app_permissions": "0", "entitlements": [], "context": 1,
+  ///   "user": { "id": "3", "username": "user", "discriminator": "0", "avatar": null },
+  ///   "data": { "id": "4", "name": "ping", "type": 1 }
+  /// })).unwrap();
+  /// let input = CommandInput::parse(interaction, Rest::new()).unwrap();
+  /// assert_eq!(input.context(), Some(InteractionContextType::BOT_DM));
+  /// ```
+  pub fn context(&self) -> Option<InteractionContextType> {
+    self.context.clone()
+  }
+
+  /// Returns which installations authorized the interaction
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// ##[command(name = "cleanup", description = "Cleans up the channel")]
+  /// fn cleanup(input: CommandInput, res: CommandResponder) {
+  ///   let guild_installed = input.integration_owners().is_some_and(|o| o.guild_id.is_some());
+  ///   if input.guild_id.is_some() && !guild_installed {
+  ///     return res.send_message("Add me to this server to use this here").await?;
+  ///   }
+  /// }
+  /// ```
+  pub fn integration_owners(&self) -> Option<&IntegrationOwners> {
+    self.authorizing_integration_owners.as_ref()
+  }
+
+  /// Returns the entitlements of the invoking user, and of the guild when the app is installed there
+  pub fn entitlements(&self) -> &[Entitlement] {
+    &self.entitlements
+  }
+
+  /// Returns true if the interaction has an [active](Entitlement::is_active) entitlement for the SKU
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// ##[command(name = "premium", description = "A premium command")]
+  /// fn premium(input: CommandInput, res: CommandResponder) {
+  ///   if !input.has_entitlement("1180218955160375406") {
+  ///     return res.send_message("This command requires premium").await?;
+  ///   }
+  ///   res.send_message("Thank you for your support!").await?;
+  /// }
+  /// ```
+  pub fn has_entitlement<T: AsRef<str>>(&self, sku_id: T) -> bool {
+    self.entitlements.iter().any(|e| e.sku_id == sku_id.as_ref() && e.is_active())
+  }
+
+  /// Returns the selected [language](https://discord.com/developers/docs/reference#locales) of the user
+  pub fn locale(&self) -> &str {
+    &self.locale
+  }
+
+  /// Returns the guild's preferred locale if the interaction was sent from a guild
+  pub fn guild_locale(&self) -> Option<&str> {
+    self.guild_locale.as_deref()
+  }
+
+  /// Returns the message the component was on for component interactions, and modal submissions opened from a component
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, MessageResponse};
+  /// # use slashook::structs::components::{Components, Button};
+  /// #[command(name = "toggle", description = "Toggles a setting")]
+  /// fn toggle(input: CommandInput, res: CommandResponder) {
+  ///   // A button with the custom_id `toggle/switch` was clicked
+  ///   let enabled = input.message().is_some_and(|m| m.content == "Enabled");
+  ///   let content = if enabled { "Disabled" } else { "Enabled" };
+  ///   let components = Components::new().add_button(Button::new().set_label("Toggle").set_id("toggle", "switch"));
+  ///   res.update_message(MessageResponse::from(content).set_components(components)).await?;
+  /// }
+  /// ```
+  pub fn message(&self) -> Option<&Message> {
+    self.message.as_ref()
+  }
+
   /// Returns true if the interaction is for an executed command
   pub fn is_command(&self) -> bool {
     matches!(self.interaction_type, InteractionType::APPLICATION_COMMAND)
@@ -456,17 +701,17 @@ impl CommandInput {
 
   /// Returns true if the interaction is for a chat input command
   pub fn is_chat_input(&self) -> bool {
-    self.command_type.as_ref().map_or(false, |t| matches!(t, ApplicationCommandType::CHAT_INPUT))
+    self.command_type.as_ref().is_some_and(|t| matches!(t, ApplicationCommandType::CHAT_INPUT))
   }
 
   /// Returns true if the interaction is for a user context menu
   pub fn is_user_context(&self) -> bool {
-    self.command_type.as_ref().map_or(false, |t| matches!(t, ApplicationCommandType::USER))
+    self.command_type.as_ref().is_some_and(|t| matches!(t, ApplicationCommandType::USER))
   }
 
   /// Returns true if the interaction is for a message context menu
   pub fn is_message_context(&self) -> bool {
-    self.command_type.as_ref().map_or(false, |t| matches!(t, ApplicationCommandType::MESSAGE))
+    self.command_type.as_ref().is_some_and(|t| matches!(t, ApplicationCommandType::MESSAGE))
   }
 
   /// Returns true if the interaction is for a message component
@@ -476,32 +721,32 @@ impl CommandInput {
 
   /// Returns true if the interaction is for a clicked button
   pub fn is_button(&self) -> bool {
-    self.component_type.as_ref().map_or(false, |t| matches!(t, ComponentType::BUTTON))
+    self.component_type.as_ref().is_some_and(|t| matches!(t, ComponentType::BUTTON))
   }
 
   /// Returns true if the interaction is for a string select menu
   pub fn is_string_select(&self) -> bool {
-    self.component_type.as_ref().map_or(false, |t| matches!(t, ComponentType::STRING_SELECT))
+    self.component_type.as_ref().is_some_and(|t| matches!(t, ComponentType::STRING_SELECT))
   }
 
   /// Returns true if the interaction is for a user select menu
   pub fn is_user_select(&self) -> bool {
-    self.component_type.as_ref().map_or(false, |t| matches!(t, ComponentType::USER_SELECT))
+    self.component_type.as_ref().is_some_and(|t| matches!(t, ComponentType::USER_SELECT))
   }
 
   /// Returns true if the interaction is for a role select menu
   pub fn is_role_select(&self) -> bool {
-    self.component_type.as_ref().map_or(false, |t| matches!(t, ComponentType::ROLE_SELECT))
+    self.component_type.as_ref().is_some_and(|t| matches!(t, ComponentType::ROLE_SELECT))
   }
 
   /// Returns true if the interaction is for a mentionable select menu
   pub fn is_mentionable_select(&self) -> bool {
-    self.component_type.as_ref().map_or(false, |t| matches!(t, ComponentType::MENTIONABLE_SELECT))
+    self.component_type.as_ref().is_some_and(|t| matches!(t, ComponentType::MENTIONABLE_SELECT))
   }
 
   /// Returns true if the interaction is for a channel select menu
   pub fn is_channel_select(&self) -> bool {
-    self.component_type.as_ref().map_or(false, |t| matches!(t, ComponentType::CHANNEL_SELECT))
+    self.component_type.as_ref().is_some_and(|t| matches!(t, ComponentType::CHANNEL_SELECT))
   }
 
   /// Returns true if the interaction is for autocompletion
@@ -515,5 +760,6 @@ impl CommandInput {
   }
 }
 
+#[cfg(feature = "server")]
 #[derive(Debug)]
-pub(crate) struct RocketCommand(pub Interaction, pub Option<String>, pub oneshot::Sender::<anyhow::Result<InteractionCallback>>);
+pub(crate) struct RocketCommand(pub Interaction, pub Option<String>, pub Option<Rest>, pub oneshot::Sender::<anyhow::Result<InteractionCallback>>);
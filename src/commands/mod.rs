@@ -15,13 +15,14 @@ use std::{
   future::Future,
   collections::HashMap,
 };
-use rocket::futures::future::BoxFuture;
+use futures::future::BoxFuture;
 
-pub use responder::{MessageResponse, CommandResponder, Modal, InteractionResponseError};
+pub use responder::{MessageResponse, CommandResponder, DeferOptions, Modal, InteractionResponseError, ResponsePolicy};
 pub use handler::CommandInput;
 use crate::structs::{
   interactions::{ApplicationCommand, ApplicationCommandHandlerType, ApplicationCommandOption, ApplicationCommandType, IntegrationType, InteractionContextType, InteractionOptionType},
-  Permissions
+  Permissions,
+  Snowflake
 };
 
 /// The `Result` types expected from a command function
@@ -82,6 +83,8 @@ pub struct Command {
   pub subcommand_groups: Option<Vec<SubcommandGroup>>,
   /// Subcommands for the command
   pub subcommands: Option<Vec<Subcommand>>,
+  /// Guilds the command is registered in instead of globally by [`sync_all_commands`](crate::Client::sync_all_commands)
+  pub guild_ids: Option<Vec<Snowflake>>,
 }
 
 /// Struct representing subcommand groups
@@ -142,7 +145,8 @@ impl Default for Command {
       contexts: None,
       handler: None,
       subcommand_groups: None,
-      subcommands: None
+      subcommands: None,
+      guild_ids: None
     }
   }
 }
@@ -165,48 +169,98 @@ impl Clone for Command {
       handler: self.handler.clone(),
       subcommand_groups: self.subcommand_groups.clone(),
       subcommands: self.subcommands.clone(),
+      guild_ids: self.guild_ids.clone(),
     }
   }
 }
 
-impl TryFrom<Command> for ApplicationCommand {
-  type Error = anyhow::Error;
-
-  fn try_from(value: Command) -> anyhow::Result<Self> {
-    if value.options.is_some() && (value.subcommands.is_some() || value.subcommand_groups.is_some()) {
+impl ApplicationCommand {
+  /// Builds the API payload for a [`Command`] by reference, leaving the command and its handler untouched
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::interactions::ApplicationCommand;
+  /// #[command(name = "example", description = "An example command")]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   res.send_message("Hi").await?;
+  /// }
+  ///
+  /// let command = ApplicationCommand::from_command_ref(&example).unwrap();
+  /// assert_eq!(command.name, "example");
+  /// assert_eq!(command.description.as_deref(), Some("An example command"));
+  /// ```
+  /// `PRIMARY_ENTRY_POINT` commands must set a `handler` and other commands can't have one.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::interactions::{ApplicationCommand, ApplicationCommandType};
+  /// #[command(name = "launch", command_type = ApplicationCommandType::PRIMARY_ENTRY_POINT)]
+  /// fn launch(input: CommandInput, res: CommandResponder) {
+  ///   res.launch_activity().await?;
+  /// }
+  ///
+  /// let error = ApplicationCommand::from_command_ref(&launch).unwrap_err();
+  /// assert_eq!(error.to_string(), "PRIMARY_ENTRY_POINT commands must have a handler");
+  /// ```
+  pub fn from_command_ref(command: &Command) -> anyhow::Result<Self> {
+    if command.options.is_some() && (command.subcommands.is_some() || command.subcommand_groups.is_some()) {
       anyhow::bail!("You cannot have options on the base command when using subcommands or subcommand groups");
     }
 
-    let mut options = value.options;
-    if let Some(scgs) = value.subcommand_groups {
-      options = Some(scgs.into_iter().map(|scg| scg.into()).collect());
+    let is_entry_point = command.command_type == Some(ApplicationCommandType::PRIMARY_ENTRY_POINT);
+    if is_entry_point && command.handler.is_none() {
+      anyhow::bail!("PRIMARY_ENTRY_POINT commands must have a handler");
+    }
+    if !is_entry_point && command.handler.is_some() {
+      anyhow::bail!("Only PRIMARY_ENTRY_POINT commands can have a handler");
     }
-    if let Some(scs) = value.subcommands {
+
+    let mut options = command.options.clone();
+    if let Some(scgs) = &command.subcommand_groups {
+      options = Some(scgs.iter().map(|scg| scg.clone().into()).collect());
+    }
+    if let Some(scs) = &command.subcommands {
       let mut opts = options.unwrap_or_default();
-      opts.extend(scs.into_iter().map(|sc| sc.into()));
+      opts.extend(scs.iter().map(|sc| sc.clone().into()));
       options = Some(opts);
     }
 
     Ok(Self {
       id: None,
-      command_type: value.command_type,
+      command_type: command.command_type.clone(),
       application_id: None,
       guild_id: None,
-      name: value.name,
-      name_localizations: value.name_localizations,
-      description: value.description.0,
-      description_localizations: value.description_localizations,
+      name: command.name.clone(),
+      name_localizations: command.name_localizations.clone(),
+      description: command.description.0.clone(),
+      description_localizations: command.description_localizations.clone(),
       options,
-      default_member_permissions: value.default_member_permissions,
-      nsfw: value.nsfw,
-      integration_types: value.integration_types,
-      contexts: value.contexts,
+      default_member_permissions: command.default_member_permissions,
+      nsfw: command.nsfw,
+      integration_types: command.integration_types.clone(),
+      contexts: command.contexts.clone(),
       version: None,
-      handler: value.handler,
+      handler: command.handler.clone(),
     })
   }
 }
 
+impl TryFrom<&Command> for ApplicationCommand {
+  type Error = anyhow::Error;
+
+  fn try_from(value: &Command) -> anyhow::Result<Self> {
+    Self::from_command_ref(value)
+  }
+}
+
+impl TryFrom<Command> for ApplicationCommand {
+  type Error = anyhow::Error;
+
+  fn try_from(value: Command) -> anyhow::Result<Self> {
+    Self::from_command_ref(&value)
+  }
+}
+
 impl From<SubcommandGroup> for ApplicationCommandOption {
   fn from(value: SubcommandGroup) -> Self {
     let options = value.subcommands.into_iter().map(|sc| sc.into()).collect();
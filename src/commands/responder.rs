@@ -6,16 +6,20 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::structs::{
-  components::{Component, Components},
+  components::{Component, Components, validate_layout},
   embeds::Embed,
   interactions::{ApplicationCommandOptionChoice, Attachments, InteractionCallbackData},
-  messages::{AllowedMentions, Attachment, Message, MessageFlags},
+  messages::{AllowedMentions, Attachment, Message, MessageFlags, MessageReference},
   polls::PollCreateRequest,
-  utils::File,
+  utils::{File, ValidationError, snowflake_timestamp},
+  Snowflake,
 };
 use serde::Serialize;
-use crate::tokio::sync::mpsc;
+use crate::tokio::{sync::mpsc, time::{sleep, timeout, Duration}};
+use std::future::Future;
 use crate::rest::{Rest, RestError};
+use reqwest::StatusCode;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 
 /// Error for when a response failed due to the interaction having been responded to already.
 #[derive(Debug)]
@@ -30,11 +34,12 @@ impl std::error::Error for InteractionResponseError { }
 /// Message that can be sent as a response to a command or other interaction
 ///
 /// This struct can be easily constructed from a `str`, `String`, [`Embed`](crate::structs::embeds::Embed), [`Components`](crate::structs::components::Components),
-/// [`File`](crate::structs::utils::File) or [`PollCreateRequest`](crate::structs::polls::PollCreateRequest)
-/// with the `From` trait
+/// [`File`](crate::structs::utils::File), [`PollCreateRequest`](crate::structs::polls::PollCreateRequest)
+/// or [`MessageReference`] with the `From` trait
 #[derive(Serialize, Clone, Debug)]
 pub struct MessageResponse {
-  /// Should the response is TTS or not
+  /// Should the response is TTS or not. Not sent when `None`, which Discord treats as `false`.
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub tts: Option<bool>,
   /// Content of the message
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -42,11 +47,13 @@ pub struct MessageResponse {
   /// Flags of the message.\
   /// Valid flags are [EPHEMERAL](crate::structs::messages::MessageFlags::EPHEMERAL) for interactions to only show the response to the invoking user and
   /// [SUPPRESS_EMBEDS](crate::structs::messages::MessageFlags::SUPPRESS_EMBEDS) to hide embeds from showing in the message.
+  /// Flags that aren't valid for channel messages or edits are removed before sending, see [`retain_flags`](Self::retain_flags).
   pub flags: Option<MessageFlags>,
   /// Up to 10 embeds to send with the response
   #[serde(skip_serializing_if = "Option::is_none")]
   pub embeds: Option<Vec<Embed>>,
-  /// Components to send with the response
+  /// Components to send with the response.\
+  /// When editing, `None` keeps the existing components and an empty Vec removes them.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub components: Option<Vec<Component>>,
   /// Partial attachment objects indicating which to keep when editing.
@@ -61,6 +68,18 @@ pub struct MessageResponse {
   /// A poll!
   #[serde(skip_serializing_if = "Option::is_none")]
   pub poll: Option<PollCreateRequest>,
+  /// Can be used to verify a message was sent. Only used when creating messages through the API.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub nonce: Option<String>,
+  /// If true and a nonce is present, Discord will check the nonce for uniqueness and return the existing message instead of sending another one
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub enforce_nonce: Option<bool>,
+  /// IDs of up to 3 stickers to send in the message. Only used when creating messages through the API.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sticker_ids: Option<Vec<Snowflake>>,
+  /// Include to make the message a reply. Only used when creating messages through the API.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub message_reference: Option<MessageReference>,
 }
 
 impl MessageResponse {
@@ -141,6 +160,32 @@ impl MessageResponse {
     self
   }
 
+  /// Removes flags that aren't in `allowed`, such as [`MessageFlags::EDIT_ALLOWED`] when editing a message.\
+  /// If every flag set is removed, the flags are left out entirely so the message's current flags aren't cleared.
+  /// [`Message::create`](crate::structs::messages::Message::create), [`Message::edit`](crate::structs::messages::Message::edit)
+  /// and the follow-up edits of [CommandResponder] already do this.
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::messages::MessageFlags;
+  /// let response = MessageResponse::from("Edited")
+  ///   .set_ephemeral(true)
+  ///   .set_suppress_embeds(true)
+  ///   .retain_flags(MessageFlags::EDIT_ALLOWED);
+  /// assert_eq!(response.flags, Some(MessageFlags::SUPPRESS_EMBEDS));
+  ///
+  /// let response = MessageResponse::from("Edited")
+  ///   .set_ephemeral(true)
+  ///   .retain_flags(MessageFlags::EDIT_ALLOWED);
+  /// assert_eq!(response.flags, None);
+  /// ```
+  pub fn retain_flags(mut self, allowed: MessageFlags) -> Self {
+    self.flags = match self.flags {
+      Some(flags) if !flags.is_empty() && (flags & allowed).is_empty() => None,
+      flags => flags.map(|flags| flags & allowed)
+    };
+    self
+  }
+
   /// Add an embed to the message
   /// ```
   /// # use slashook::commands::MessageResponse;
@@ -181,12 +226,50 @@ impl MessageResponse {
   /// let response = MessageResponse::from("Ooh! A big red button!")
   ///   .set_components(components);
   /// ```
+  /// Action rows that were left empty are dropped:
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::components::{Components, Button};
+  /// let components = Components::new()
+  ///   .add_button(Button::new())
+  ///   .add_row();
+  /// let response = MessageResponse::from("Only one row")
+  ///   .set_components(components);
+  /// assert_eq!(response.components.unwrap().len(), 1);
+  /// ```
   pub fn set_components(mut self, components: Components) -> Self {
-    self.components = Some(components.0);
+    self.components = Some(components.into());
+    self
+  }
+
+  /// Keep the existing components when editing. Unsets components so they're left out of the request, which is also the default.
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::components::{Components, Button};
+  /// let response = MessageResponse::from("Only the content changes")
+  ///   .set_components(Components::new().add_button(Button::new()))
+  ///   .keep_components();
+  /// assert!(response.components.is_none());
+  /// ```
+  pub fn keep_components(mut self) -> Self {
+    self.components = None;
     self
   }
 
-  /// Set the allowed mentions for the message
+  /// Clear components from the message. Sets components to an empty Vec which also removes components when editing.
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// let response = MessageResponse::from("Buttons removed")
+  ///   .clear_components();
+  /// assert_eq!(response.components.unwrap().len(), 0);
+  /// ```
+  pub fn clear_components(mut self) -> Self {
+    self.components = Some(Vec::new());
+    self
+  }
+
+  /// Set the allowed mentions for the message.\
+  /// This applies to the initial interaction response as well as follow-ups and regular messages.
   /// ```
   /// # use slashook::commands::MessageResponse;
   /// # use slashook::structs::messages::{AllowedMentions, AllowedMentionType};
@@ -194,6 +277,15 @@ impl MessageResponse {
   /// let response = MessageResponse::from("<@1234> Get pinged. Not @everyone or <@&1235> tho.")
   ///   .set_allowed_mentions(allowed_mentions);
   /// ```
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::{interactions::InteractionCallbackData, messages::AllowedMentions};
+  /// // The initial response keeps the allowed mentions
+  /// let response = MessageResponse::from("Nobody gets pinged, not even <@1234>")
+  ///   .set_allowed_mentions(AllowedMentions::new());
+  /// let data = serde_json::to_value(InteractionCallbackData::from(response)).unwrap();
+  /// assert_eq!(data["allowed_mentions"]["parse"], serde_json::json!([]));
+  /// ```
   pub fn set_allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
     self.allowed_mentions = Some(allowed_mentions);
     self
@@ -214,6 +306,15 @@ impl MessageResponse {
   /// # Ok(())
   /// # }
   /// ```
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::{interactions::InteractionCallbackData, utils::File};
+  /// // Files can be sent with the initial response without deferring first
+  /// let response = MessageResponse::from("Here's the log")
+  ///   .add_file(File::new("log.txt", "Everything is fine"));
+  /// let data = InteractionCallbackData::from(response);
+  /// assert_eq!(data.files.as_ref().unwrap()[0].filename, "log.txt");
+  /// ```
   pub fn add_file(mut self, file: File) -> Self {
     let mut files = self.files.unwrap_or_default();
     files.push(file);
@@ -280,6 +381,211 @@ impl MessageResponse {
     self.poll = Some(poll);
     self
   }
+
+  /// Set the nonce of the message
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// let response = MessageResponse::from("Only sent once")
+  ///   .set_nonce(1234);
+  /// assert_eq!(response.nonce, Some(String::from("1234")));
+  /// ```
+  pub fn set_nonce<T: ToString>(mut self, nonce: T) -> Self {
+    self.nonce = Some(nonce.to_string());
+    self
+  }
+
+  /// Set whether the nonce should be enforced to avoid sending duplicate messages
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// let response = MessageResponse::from("Only sent once")
+  ///   .set_nonce("unique-nonce")
+  ///   .set_enforce_nonce(true);
+  /// assert_eq!(response.enforce_nonce, Some(true));
+  /// ```
+  pub fn set_enforce_nonce(mut self, enforce_nonce: bool) -> Self {
+    self.enforce_nonce = Some(enforce_nonce);
+    self
+  }
+
+  /// Add a sticker to the message
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// let response = MessageResponse::from("Look at this sticker")
+  ///   .add_sticker("749054660769218631");
+  /// assert_eq!(response.sticker_ids.unwrap()[0], "749054660769218631");
+  /// ```
+  pub fn add_sticker<T: ToString>(mut self, sticker_id: T) -> Self {
+    let mut sticker_ids = self.sticker_ids.unwrap_or_default();
+    sticker_ids.push(sticker_id.to_string());
+    self.sticker_ids = Some(sticker_ids);
+    self
+  }
+
+  /// Set a message reference to make the message a reply or a [forward](MessageReference::new_forward). See also [`Message::reply`]
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::messages::MessageReference;
+  /// let response = MessageResponse::from("I agree")
+  ///   .set_message_reference(MessageReference {
+  ///     message_id: Some(String::from("916413462467465246")),
+  ///     channel_id: None,
+  ///     guild_id: None,
+  ///     fail_if_not_exists: Some(false),
+  ///     reference_type: None,
+  ///   });
+  /// ```
+  pub fn set_message_reference(mut self, message_reference: MessageReference) -> Self {
+    self.message_reference = Some(message_reference);
+    self
+  }
+
+  /// Checks the message against the rules Discord has for creating messages
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::embeds::Embed;
+  /// let response = MessageResponse::from("Too many stickers")
+  ///   .add_sticker("749054660769218631")
+  ///   .add_sticker("749054660769218632")
+  ///   .add_sticker("749054660769218633")
+  ///   .add_sticker("749054660769218634");
+  /// assert!(response.validate().is_err());
+  ///
+  /// let response = MessageResponse::from(Embed::new().set_description("Just fine"));
+  /// assert!(response.validate().is_ok());
+  /// ```
+  pub fn validate(&self) -> Result<(), ValidationError> {
+    let has_content = self.content.as_ref().is_some_and(|c| !c.is_empty());
+    let has_embeds = self.embeds.as_ref().is_some_and(|e| !e.is_empty());
+    let has_stickers = self.sticker_ids.as_ref().is_some_and(|s| !s.is_empty());
+    let has_components = self.components.as_ref().is_some_and(|c| !c.is_empty());
+    let has_files = self.files.as_ref().is_some_and(|f| !f.is_empty());
+
+    let is_forward = self.message_reference.as_ref().is_some_and(|r| r.is_forward());
+
+    if !has_content && !has_embeds && !has_stickers && !has_components && !has_files && self.poll.is_none() && !is_forward {
+      return Err(ValidationError(String::from("A message must have at least one of content, embeds, stickers, components, files or a poll")));
+    }
+
+    self.validate_limits()
+  }
+
+  /// Checks the message against the length and count limits Discord has for messages and the [component layout rules](Components::validate).\
+  /// Unlike [`validate`](MessageResponse::validate), an empty message passes, so this also works for edits.
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// let response = MessageResponse::from("a".repeat(2001));
+  /// assert!(response.validate_limits().is_err());
+  /// assert!(MessageResponse::from("a".repeat(2000)).validate_limits().is_ok());
+  /// ```
+  pub fn validate_limits(&self) -> Result<(), ValidationError> {
+    if let Some(content) = &self.content {
+      let length = content.chars().count();
+      if length > 2000 {
+        return Err(ValidationError(format!("Message content can be up to 2000 characters, found {}", length)));
+      }
+    }
+
+    if let Some(sticker_ids) = &self.sticker_ids {
+      if sticker_ids.len() > 3 {
+        return Err(ValidationError(format!("A message can have up to 3 stickers, found {}", sticker_ids.len())));
+      }
+    }
+
+    if let Some(embeds) = &self.embeds {
+      if embeds.len() > 10 {
+        return Err(ValidationError(format!("A message can have up to 10 embeds, found {}", embeds.len())));
+      }
+    }
+
+    if let Some(files) = &self.files {
+      if files.len() > 10 {
+        return Err(ValidationError(format!("A message can have up to 10 files, found {}", files.len())));
+      }
+    }
+
+    if let Some(components) = &self.components {
+      let rows: Vec<&Component> = components.iter().collect();
+      validate_layout(&rows)?;
+      let has_text_input = rows.iter().any(|c| match c {
+        Component::ActionRow(row) => row.components.iter().any(|c| matches!(c, Component::TextInput(_))),
+        _ => false
+      });
+      if has_text_input {
+        return Err(ValidationError(String::from("Text inputs can only be used in modals")));
+      }
+    }
+
+    if self.nonce.is_none() && self.enforce_nonce == Some(true) {
+      return Err(ValidationError(String::from("enforce_nonce requires a nonce to be set")));
+    }
+
+    self.validate_forward()
+  }
+
+  /// Checks that a forwarding message reference points to a message and that the message has nothing else to send with it
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::messages::MessageReference;
+  /// let mut reference = MessageReference::new_forward("697138785317814292", "916413462467465246");
+  /// let response = MessageResponse::from("Look at this").set_message_reference(reference.clone());
+  /// assert_eq!(response.validate_forward().unwrap_err().0, "A forwarded message can't have content, embeds, stickers, components, files or a poll");
+  ///
+  /// reference.channel_id = None;
+  /// let response = MessageResponse::from(reference);
+  /// assert_eq!(response.validate_forward().unwrap_err().0, "A forward reference must have both a message_id and a channel_id");
+  /// ```
+  pub fn validate_forward(&self) -> Result<(), ValidationError> {
+    let Some(reference) = self.message_reference.as_ref().filter(|r| r.is_forward()) else { return Ok(()) };
+
+    if reference.message_id.is_none() || reference.channel_id.is_none() {
+      return Err(ValidationError(String::from("A forward reference must have both a message_id and a channel_id")));
+    }
+
+    let has_content = self.content.as_ref().is_some_and(|c| !c.is_empty());
+    let has_embeds = self.embeds.as_ref().is_some_and(|e| !e.is_empty());
+    let has_stickers = self.sticker_ids.as_ref().is_some_and(|s| !s.is_empty());
+    let has_components = self.components.as_ref().is_some_and(|c| !c.is_empty());
+    let has_files = self.files.as_ref().is_some_and(|f| !f.is_empty());
+
+    if has_content || has_embeds || has_stickers || has_components || has_files || self.poll.is_some() {
+      return Err(ValidationError(String::from("A forwarded message can't have content, embeds, stickers, components, files or a poll")));
+    }
+
+    Ok(())
+  }
+
+  /// Creates an ephemeral message telling the user why a request failed\
+  /// Missing permissions, missing resources, rate limits and validation errors get their own messages and everything else gets a generic one.
+  /// The same conversion is available with `From<&RestError>`.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, MessageResponse};
+  /// # use slashook::structs::guilds::Guild;
+  /// ##[command(name = "leave", description = "Makes the bot leave")]
+  /// fn leave(input: CommandInput, res: CommandResponder) {
+  ///   if let Err(err) = Guild::leave(&input.rest, input.guild_id.unwrap()).await {
+  ///     return res.send_message(MessageResponse::from_error(&err)).await?;
+  ///   }
+  /// }
+  /// ```
+  /// ```
+  /// # use slashook::{commands::MessageResponse, rest::RestError};
+  /// # use slashook::structs::messages::MessageFlags;
+  /// let err = RestError::RequestFailed { status: reqwest::StatusCode::FORBIDDEN, body: String::new() };
+  /// let response = MessageResponse::from(&err);
+  /// assert_eq!(response.content.unwrap(), "I don't have permission to do that");
+  /// assert!(response.flags.unwrap().contains(MessageFlags::EPHEMERAL));
+  /// ```
+  pub fn from_error(error: &RestError) -> Self {
+    let content = match error {
+      RestError::RequestFailed { status: StatusCode::FORBIDDEN, .. } => String::from("I don't have permission to do that"),
+      RestError::RequestFailed { status: StatusCode::NOT_FOUND, .. } => String::from("That no longer exists"),
+      RestError::RequestFailed { status: StatusCode::TOO_MANY_REQUESTS, .. } => String::from("I'm being rate limited, try again shortly"),
+      RestError::ValidationError(err) => format!("That couldn't be done: {}", err.0),
+      _ => String::from("Something went wrong, try again later")
+    };
+    MessageResponse::from(content).set_ephemeral(true)
+  }
 }
 
 /// A modal that can be opened for user input
@@ -321,9 +627,201 @@ impl Modal {
   ///   .set_components(components);
   /// ```
   pub fn set_components(mut self, components: Components) -> Self {
-    self.components = components.0;
+    self.components = components.into();
+    self
+  }
+
+  /// Checks the modal against the rules Discord has for modals and returns the first one broken.\
+  /// A modal needs 1 to 5 action rows that each hold a single text input with valid lengths, and the custom_ids must be unique.
+  /// ```
+  /// # use slashook::commands::Modal;
+  /// # use slashook::structs::components::{Components, TextInput, Button};
+  /// let modal = Modal::new("example_command", "modal1", "Please fill this form")
+  ///   .set_components(Components::new().add_text_input(TextInput::new().set_label("Name").set_id("name")));
+  /// assert!(modal.validate().is_ok());
+  ///
+  /// let bad_lengths = TextInput::new().set_label("Bio").set_id("bio").set_min_length(100).set_max_length(10);
+  /// let modal = modal.set_components(Components::new().add_text_input(bad_lengths));
+  /// assert!(modal.validate().is_err());
+  ///
+  /// let modal = Modal::new("example_command", "modal1", "Buttons don't work here")
+  ///   .set_components(Components::new().add_button(Button::new().set_label("Nope").set_id("example_command", "nope")));
+  /// assert!(modal.validate().is_err());
+  /// ```
+  pub fn validate(&self) -> Result<(), ValidationError> {
+    let title_length = self.title.chars().count();
+    if title_length == 0 || title_length > 45 {
+      return Err(ValidationError(format!("Modal title must be 1-45 characters, found {}", title_length)));
+    }
+    if self.custom_id.chars().count() > 100 {
+      return Err(ValidationError(String::from("Modal custom_id can be up to 100 characters")));
+    }
+    if self.components.is_empty() || self.components.len() > 5 {
+      return Err(ValidationError(format!("A modal must have 1-5 action rows, found {}", self.components.len())));
+    }
+
+    let mut custom_ids = Vec::new();
+    for (i, component) in self.components.iter().enumerate() {
+      let Component::ActionRow(row) = component else {
+        return Err(ValidationError(format!("Modal component {} must be an action row", i)));
+      };
+      let [Component::TextInput(input)] = &row.components[..] else {
+        return Err(ValidationError(format!("Action row {} in a modal must contain exactly one text input", i)));
+      };
+
+      let label_length = input.label.chars().count();
+      if label_length == 0 || label_length > 45 {
+        return Err(ValidationError(format!("Text input {} label must be 1-45 characters, found {}", input.custom_id, label_length)));
+      }
+      let id_length = input.custom_id.chars().count();
+      if id_length == 0 || id_length > 100 {
+        return Err(ValidationError(format!("Text input custom_id must be 1-100 characters, found {}", id_length)));
+      }
+      if custom_ids.contains(&&input.custom_id) {
+        return Err(ValidationError(format!("Text input custom_id {} is used more than once", input.custom_id)));
+      }
+      custom_ids.push(&input.custom_id);
+
+      if input.min_length.is_some_and(|min| !(0..=4000).contains(&min)) {
+        return Err(ValidationError(format!("Text input {} min_length must be 0-4000", input.custom_id)));
+      }
+      if input.max_length.is_some_and(|max| !(1..=4000).contains(&max)) {
+        return Err(ValidationError(format!("Text input {} max_length must be 1-4000", input.custom_id)));
+      }
+      if let (Some(min), Some(max)) = (input.min_length, input.max_length) {
+        if min > max {
+          return Err(ValidationError(format!("Text input {} min_length is greater than max_length", input.custom_id)));
+        }
+      }
+      if let Some(value) = &input.value {
+        let value_length = value.chars().count() as i64;
+        if value_length > input.max_length.unwrap_or(4000) {
+          return Err(ValidationError(format!("Text input {} value is longer than allowed", input.custom_id)));
+        }
+      }
+      if input.placeholder.as_ref().is_some_and(|p| p.chars().count() > 100) {
+        return Err(ValidationError(format!("Text input {} placeholder can be up to 100 characters", input.custom_id)));
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Options for deferring a response with [`defer_with`](CommandResponder::defer_with)
+#[derive(Clone, Debug)]
+pub struct DeferOptions {
+  /// Whether to defer an update to the message the component is on instead of sending a new message
+  pub update: bool,
+  /// Flags for the response, such as [EPHEMERAL](MessageFlags::EPHEMERAL).\
+  /// Ignored when deferring an update since Discord doesn't apply flags to the message the component is on.
+  pub flags: MessageFlags,
+}
+
+impl DeferOptions {
+  /// Creates new DeferOptions for deferring a new message without any flags
+  pub fn new() -> Self {
+    Self {
+      update: false,
+      flags: MessageFlags::empty(),
+    }
+  }
+
+  /// Sets whether to defer an update instead of a new message
+  pub fn set_update(mut self, update: bool) -> Self {
+    self.update = update;
+    self
+  }
+
+  /// Sets the ephemeral flag
+  pub fn set_ephemeral(mut self, ephemeral: bool) -> Self {
+    self.flags.set(MessageFlags::EPHEMERAL, ephemeral);
+    self
+  }
+
+  /// Sets the flags, replacing any that were set before
+  pub fn set_flags(mut self, flags: MessageFlags) -> Self {
+    self.flags = flags;
+    self
+  }
+}
+
+impl Default for DeferOptions {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Defaults a [CommandResponder] applies to the messages it sends, see [`CommandResponder::with_policy`]
+///
+/// Flags are added to the flags of each message and `tts` is used when a message doesn't set it.
+/// A per-message nonce isn't included since reusing one would make Discord treat later messages as duplicates.
+/// ```
+/// # use slashook::commands::{MessageResponse, ResponsePolicy};
+/// # use slashook::structs::messages::MessageFlags;
+/// let policy = ResponsePolicy::new()
+///   .set_tts(false)
+///   .set_ephemeral(true)
+///   .add_flags(MessageFlags::SUPPRESS_NOTIFICATIONS);
+/// let response = policy.apply(MessageResponse::from("Quiet").set_suppress_embeds(true));
+/// assert_eq!(response.tts, Some(false));
+/// assert_eq!(response.flags, Some(MessageFlags::EPHEMERAL | MessageFlags::SUPPRESS_NOTIFICATIONS | MessageFlags::SUPPRESS_EMBEDS));
+///
+/// let loud = ResponsePolicy::new().set_tts(true);
+/// assert_eq!(loud.apply(MessageResponse::from("Hello")).tts, Some(true));
+/// assert_eq!(loud.apply(MessageResponse::from("Shh").set_tts(false)).tts, Some(false));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ResponsePolicy {
+  /// Whether messages are sent as TTS unless they say otherwise
+  pub tts: Option<bool>,
+  /// Flags added to every message
+  pub flags: MessageFlags
+}
+
+impl ResponsePolicy {
+  /// Creates a new policy that doesn't change anything
+  pub fn new() -> Self {
+    Self {
+      tts: None,
+      flags: MessageFlags::empty()
+    }
+  }
+
+  /// Sets the default for TTS
+  pub fn set_tts(mut self, tts: bool) -> Self {
+    self.tts = Some(tts);
+    self
+  }
+
+  /// Sets whether every message is ephemeral, including deferred responses
+  pub fn set_ephemeral(mut self, ephemeral: bool) -> Self {
+    self.flags.set(MessageFlags::EPHEMERAL, ephemeral);
+    self
+  }
+
+  /// Adds flags to every message
+  pub fn add_flags(mut self, flags: MessageFlags) -> Self {
+    self.flags |= flags;
     self
   }
+
+  /// Applies the policy to a message
+  pub fn apply(&self, mut response: MessageResponse) -> MessageResponse {
+    if response.tts.is_none() {
+      response.tts = self.tts;
+    }
+    if !self.flags.is_empty() {
+      response.flags = Some(response.flags.unwrap_or_else(MessageFlags::empty) | self.flags);
+    }
+    response
+  }
+}
+
+impl Default for ResponsePolicy {
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 #[derive(Debug)]
@@ -335,20 +833,42 @@ pub enum CommandResponse {
   AutocompleteResult(Vec<ApplicationCommandOptionChoice>),
   Modal(Modal),
   LaunchActivity,
+  PremiumRequired,
 }
 
 /// Struct with methods for responding to interactions
 #[derive(Debug)]
 pub struct CommandResponder {
   pub(crate) tx: mpsc::UnboundedSender<CommandResponse>,
+  pub(crate) interaction_id: String,
   pub(crate) id: String,
   pub(crate) token: String,
-  pub(crate) rest: Rest
+  pub(crate) rest: Rest,
+  pub(crate) policy: ResponsePolicy
 }
 
 impl CommandResponder {
+  /// Sets defaults for the messages sent with this responder, such as making every response ephemeral.\
+  /// The policy is applied to messages sent with [`send_message`](CommandResponder::send_message) and the follow-up methods,
+  /// and its ephemeral flag also to [`defer`](CommandResponder::defer).
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, ResponsePolicy};
+  /// # use slashook::structs::messages::MessageFlags;
+  /// ##[command(name = "example", description = "An example command")]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   let res = res.with_policy(ResponsePolicy::new().set_tts(false).add_flags(MessageFlags::SUPPRESS_NOTIFICATIONS));
+  ///   res.send_message("Nobody gets notified about this").await?;
+  /// }
+  /// ```
+  pub fn with_policy(mut self, policy: ResponsePolicy) -> Self {
+    self.policy = policy;
+    self
+  }
+
   /// Respond to an interaction with a message.\
-  /// If interaction has already been responded to, this function will call [`send_followup_message`](CommandResponder::send_followup_message) instead and a message can only be returned in this case.
+  /// If interaction has already been responded to, this function will call [`send_followup_message`](CommandResponder::send_followup_message) instead and a message can only be returned in this case.\
+  /// A message that breaks Discord's limits is returned as a [ValidationError](RestError::ValidationError) without being sent.
   /// ```
   /// # #[macro_use] extern crate slashook;
   /// # use slashook::commands::{CommandInput, CommandResponder};
@@ -358,7 +878,8 @@ impl CommandResponder {
   /// }
   /// ```
   pub async fn send_message<T: Into<MessageResponse>>(&self, response: T) -> Result<Option<Message>, RestError> {
-    let response = response.into();
+    let response = self.policy.apply(response.into());
+    response.validate()?;
     match self.tx.send(CommandResponse::SendMessage(response)) {
       Ok(_) => {
         self.tx.closed().await;
@@ -374,7 +895,8 @@ impl CommandResponder {
   }
 
   /// Respond to an interaction by editing the original message.\
-  /// If interaction has already been responded to, this function will call [`edit_original_message`](CommandResponder::edit_original_message) instead and a message can only be returned in this case.
+  /// If interaction has already been responded to, this function will call [`edit_original_message`](CommandResponder::edit_original_message) instead and a message can only be returned in this case.\
+  /// A message that breaks Discord's limits is returned as a [ValidationError](RestError::ValidationError) without being sent.
   /// ```
   /// # #[macro_use] extern crate slashook;
   /// # use slashook::commands::{CommandInput, CommandResponder, MessageResponse};
@@ -386,6 +908,7 @@ impl CommandResponder {
   /// ```
   pub async fn update_message<T: Into<MessageResponse>>(&self, response: T) -> Result<Option<Message>, RestError> {
     let response = response.into();
+    response.validate_limits()?;
     match self.tx.send(CommandResponse::UpdateMessage(response)) {
       Ok(_) => {
         self.tx.closed().await;
@@ -400,8 +923,50 @@ impl CommandResponder {
     }
   }
 
+  /// Same as [`send_message`](CommandResponder::send_message) but always returns the sent message.\
+  /// When the message is the initial response, it is fetched with [`get_original_message`](CommandResponder::get_original_message) after the response has been delivered.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// ##[command(name = "example", description = "An example command")]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   let msg = res.send_message_with_response("Hello!").await?;
+  ///   println!("Sent message {}", msg.id);
+  /// }
+  /// ```
+  pub async fn send_message_with_response<T: Into<MessageResponse>>(&self, response: T) -> Result<Message, RestError> {
+    match self.send_message(response).await? {
+      Some(msg) => Ok(msg),
+      None => self.fetch_original_after_response().await
+    }
+  }
+
+  /// Same as [`update_message`](CommandResponder::update_message) but always returns the updated message.\
+  /// When the update is the initial response, the message is fetched with [`get_original_message`](CommandResponder::get_original_message) after the response has been delivered.
+  pub async fn update_message_with_response<T: Into<MessageResponse>>(&self, response: T) -> Result<Message, RestError> {
+    match self.update_message(response).await? {
+      Some(msg) => Ok(msg),
+      None => self.fetch_original_after_response().await
+    }
+  }
+
+  async fn fetch_original_after_response(&self) -> Result<Message, RestError> {
+    // The initial response is sent back to Discord after the handler has given it to us, so it might not exist quite yet
+    let mut attempts = 0;
+    loop {
+      match self.get_original_message().await {
+        Err(RestError::RequestFailed { status, .. }) if status == StatusCode::NOT_FOUND && attempts < 3 => {
+          attempts += 1;
+          sleep(Duration::from_millis(250 * attempts)).await;
+        },
+        result => return result
+      }
+    }
+  }
+
   /// Give yourself more execution time.\
   /// If you don't respond within 3 seconds, Discord will disconnect and tell the user the interaction failed to run.
+  /// A response that misses that window can't be salvaged later, so defer before doing anything that might take longer.
   /// By deferring, Discord will tell the user your bot is "thinking" and allow you to take your time. You can use the `send_followup_message` or `edit_original_message` methods to send the response.\
   /// The ephemeralness set here will be passed on to your first follow-up, no matter what ephemeralness you set there.
   /// ```
@@ -415,11 +980,7 @@ impl CommandResponder {
   /// }
   /// ```
   pub async fn defer(&self, ephemeral: bool) -> Result<(), InteractionResponseError> {
-    let mut flags = MessageFlags::empty();
-    flags.set(MessageFlags::EPHEMERAL, ephemeral);
-    self.tx.send(CommandResponse::DeferMessage(flags)).map_err(|_| InteractionResponseError)?;
-    self.tx.closed().await;
-    Ok(())
+    self.defer_with(DeferOptions::new().set_ephemeral(ephemeral)).await
   }
 
   /// Much like `defer` but for component interactions and it shows nothing visibly to the user.
@@ -434,7 +995,28 @@ impl CommandResponder {
   /// }
   /// ```
   pub async fn defer_update(&self) -> Result<(), InteractionResponseError> {
-    self.tx.send(CommandResponse::DeferUpdate).map_err(|_| InteractionResponseError)?;
+    self.defer_with(DeferOptions::new().set_update(true)).await
+  }
+
+  /// Defers the response with options, covering both [`defer`](CommandResponder::defer) and [`defer_update`](CommandResponder::defer_update).\
+  /// Updates are sent as `DEFERRED_UPDATE_MESSAGE` without any flags and everything else as `DEFERRED_CHANNEL_MESSAGE_WITH_SOURCE`.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, DeferOptions};
+  /// ##[command(name = "example", description = "An example command")]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   res.defer_with(DeferOptions::new().set_ephemeral(true)).await?;
+  ///   // Do something that takes longer than 3s
+  ///   res.send_message("Only you can see this").await?;
+  /// }
+  /// ```
+  pub async fn defer_with(&self, options: DeferOptions) -> Result<(), InteractionResponseError> {
+    let response = if options.update {
+      CommandResponse::DeferUpdate
+    } else {
+      CommandResponse::DeferMessage(options.flags | (self.policy.flags & MessageFlags::EPHEMERAL))
+    };
+    self.tx.send(response).map_err(|_| InteractionResponseError)?;
     self.tx.closed().await;
     Ok(())
   }
@@ -466,6 +1048,32 @@ impl CommandResponder {
     Ok(())
   }
 
+  /// Respond to an autocomplete interaction with choices from a future, or with no choices if it doesn't finish before the deadline.\
+  /// Autocomplete interactions can't be deferred, so this makes sure a response is sent within Discord's 3 second window even when the data source is slow.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, MessageResponse};
+  /// # use slashook::structs::interactions::{ApplicationCommandOptionChoice, InteractionOptionType};
+  /// # use slashook::tokio::time::Duration;
+  /// ##[command(name = "example", description = "An example command", options = [{
+  ///   name = "choice", description = "Choose an option",
+  ///   autocomplete = true, option_type = InteractionOptionType::STRING
+  /// }])]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   if input.is_autocomplete() {
+  ///     let fetch_choices = async {
+  ///       // Fetch choices from a slow API here
+  ///       vec![ApplicationCommandOptionChoice::new("A slow choice", "slow1")]
+  ///     };
+  ///     return res.autocomplete_or_empty(fetch_choices, Duration::from_millis(2500)).await?;
+  ///   }
+  /// }
+  /// ```
+  pub async fn autocomplete_or_empty<F: Future<Output = Vec<ApplicationCommandOptionChoice>>>(&self, choices: F, deadline: Duration) -> Result<(), InteractionResponseError> {
+    let results = timeout(deadline, choices).await.unwrap_or_default();
+    self.autocomplete(results).await
+  }
+
   /// Respond to an interaction with a modal
   /// ```
   /// # #[macro_use] extern crate slashook;
@@ -508,6 +1116,54 @@ impl CommandResponder {
     Ok(())
   }
 
+  /// Respond to an interaction with Discord's upsell prompt for premium features.\
+  /// Only available for apps with monetization enabled.
+  /// Discord has deprecated this response in favor of [premium buttons](crate::structs::components::ButtonStyle::PREMIUM), which should be preferred where possible.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// ##[command(name = "premium", description = "A premium command")]
+  /// fn premium(input: CommandInput, res: CommandResponder) {
+  ///   if input.entitlements.is_empty() {
+  ///     return res.premium_required().await?;
+  ///   }
+  ///   res.send_message("Thank you for your support!").await?;
+  /// }
+  /// ```
+  pub async fn premium_required(&self) -> Result<(), InteractionResponseError> {
+    self.tx.send(CommandResponse::PremiumRequired).map_err(|_| InteractionResponseError)?;
+    self.tx.closed().await;
+    Ok(())
+  }
+
+  /// Returns the time the interaction token expires at, after which follow-up messages can no longer be sent or edited.\
+  /// Interaction tokens are valid for 15 minutes after the interaction was created.
+  pub fn token_expires_at(&self) -> DateTime<Utc> {
+    let created_at = snowflake_timestamp(&self.interaction_id).unwrap_or_else(Utc::now);
+    created_at + ChronoDuration::minutes(15)
+  }
+
+  /// Returns true if the interaction token has expired
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::channels::Channel;
+  /// ##[command(name = "example", description = "An example command")]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   res.defer(false).await?;
+  ///   // Do something that takes a long time
+  ///   if res.is_token_expired() {
+  ///     let channel = Channel::fetch(&input.rest, input.channel_id.unwrap()).await?;
+  ///     channel.create_message(&input.rest, "Finally done!").await?;
+  ///   } else {
+  ///     res.send_followup_message("Finally done!").await?;
+  ///   }
+  /// }
+  /// ```
+  pub fn is_token_expired(&self) -> bool {
+    Utc::now() >= self.token_expires_at()
+  }
+
   /// Send more messages after the initial response
   /// ```
   /// # #[macro_use] extern crate slashook;
@@ -519,10 +1175,32 @@ impl CommandResponder {
   /// }
   /// ```
   pub async fn send_followup_message<T: Into<MessageResponse>>(&self, response: T) -> Result<Message, RestError> {
-    let mut response = response.into();
+    self.post_followup(None, response.into()).await
+  }
+
+  /// Send a follow-up message to a thread in the channel the webhook belongs to
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, MessageResponse};
+  /// ##[command(name = "example", description = "An example command")]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   res.send_message("Results will be posted in the log thread").await?;
+  ///   res.send_followup_message_in_thread("1228314225284366376", "Here are the results").await?;
+  /// }
+  /// ```
+  pub async fn send_followup_message_in_thread<T: ToString, U: Into<MessageResponse>>(&self, thread_id: T, response: U) -> Result<Message, RestError> {
+    self.post_followup(Some(thread_id.to_string()), response.into()).await
+  }
+
+  fn thread_query(thread_id: Option<String>) -> String {
+    thread_id.map(|id| format!("?thread_id={}", id)).unwrap_or_default()
+  }
+
+  async fn post_followup(&self, thread_id: Option<String>, response: MessageResponse) -> Result<Message, RestError> {
+    let mut response = self.policy.apply(response);
     let files = response.files.take();
     let msg: InteractionCallbackData = response.into();
-    let path = format!("webhooks/{}/{}", self.id, self.token);
+    let path = format!("webhooks/{}/{}{}", self.id, self.token, Self::thread_query(thread_id));
     if let Some(files) = files {
       self.rest.post_files(path, msg, files).await
     } else {
@@ -542,10 +1220,34 @@ impl CommandResponder {
   /// }
   /// ```
   pub async fn edit_followup_message<T: Into<MessageResponse>>(&self, id: String, response: T) -> Result<Message, RestError> {
-    let mut response = response.into();
+    self.patch_followup(id, None, response.into()).await
+  }
+
+  /// Edits a follow-up message that was sent to a thread
+  pub async fn edit_followup_message_in_thread<T: ToString, U: Into<MessageResponse>>(&self, thread_id: T, id: String, response: U) -> Result<Message, RestError> {
+    self.patch_followup(id, Some(thread_id.to_string()), response.into()).await
+  }
+
+  /// Edits a previously sent message, taking the id from the message object
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, MessageResponse};
+  /// ##[command(name = "example", description = "An example command")]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   res.send_message("First message!").await?;
+  ///   let msg = res.send_followup_message("Second message!").await?;
+  ///   res.edit_message(&msg, "Second message but edited!").await?;
+  /// }
+  /// ```
+  pub async fn edit_message<T: Into<MessageResponse>>(&self, message: &Message, response: T) -> Result<Message, RestError> {
+    self.edit_followup_message(message.id.clone(), response).await
+  }
+
+  async fn patch_followup(&self, id: String, thread_id: Option<String>, response: MessageResponse) -> Result<Message, RestError> {
+    let mut response = response.retain_flags(MessageFlags::EDIT_ALLOWED);
     let files = response.files.take();
     let msg: InteractionCallbackData = response.into();
-    let path = format!("webhooks/{}/{}/messages/{}", self.id, self.token, id);
+    let path = format!("webhooks/{}/{}/messages/{}{}", self.id, self.token, id, Self::thread_query(thread_id));
     if let Some(files) = files {
       self.rest.patch_files(path, msg, files).await
     } else {
@@ -595,6 +1297,26 @@ impl CommandResponder {
     self.rest.delete(format!("webhooks/{}/{}/messages/{}", self.id, self.token, id)).await
   }
 
+  /// Deletes a follow-up message that was sent to a thread
+  pub async fn delete_followup_message_in_thread<T: ToString>(&self, thread_id: T, id: String) -> Result<(), RestError> {
+    self.rest.delete(format!("webhooks/{}/{}/messages/{}{}", self.id, self.token, id, Self::thread_query(Some(thread_id.to_string())))).await
+  }
+
+  /// Deletes a previously sent message, taking the id from the message object
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, MessageResponse};
+  /// ##[command(name = "example", description = "An example command")]
+  /// fn example(input: CommandInput, res: CommandResponder) {
+  ///   res.send_message("First message!").await?;
+  ///   let msg = res.send_followup_message("If you see me say hi").await?;
+  ///   res.delete_message(&msg).await?;
+  /// }
+  /// ```
+  pub async fn delete_message(&self, message: &Message) -> Result<(), RestError> {
+    self.delete_followup_message(message.id.clone()).await
+  }
+
   /// Deletes the original message\
   /// Same as running `delete_followup_message` with id of `@original`
   pub async fn delete_original_message(&self) -> Result<(), RestError> {
@@ -605,7 +1327,7 @@ impl CommandResponder {
 impl From<&str> for MessageResponse {
   fn from(s: &str) -> MessageResponse {
     MessageResponse {
-      tts: Some(false),
+      tts: None,
       content: Some(String::from(s)),
       flags: None,
       embeds: None,
@@ -614,6 +1336,10 @@ impl From<&str> for MessageResponse {
       allowed_mentions: None,
       files: None,
       poll: None,
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: None,
     }
   }
 }
@@ -621,7 +1347,7 @@ impl From<&str> for MessageResponse {
 impl From<String> for MessageResponse {
   fn from(s: String) -> MessageResponse {
     MessageResponse {
-      tts: Some(false),
+      tts: None,
       content: Some(s),
       flags: None,
       embeds: None,
@@ -630,6 +1356,10 @@ impl From<String> for MessageResponse {
       allowed_mentions: None,
       files: None,
       poll: None,
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: None,
     }
   }
 }
@@ -637,7 +1367,7 @@ impl From<String> for MessageResponse {
 impl From<Embed> for MessageResponse {
   fn from(e: Embed) -> MessageResponse {
     MessageResponse {
-      tts: Some(false),
+      tts: None,
       content: None,
       flags: None,
       embeds: Some(vec![e]),
@@ -646,6 +1376,10 @@ impl From<Embed> for MessageResponse {
       allowed_mentions: None,
       files: None,
       poll: None,
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: None,
     }
   }
 }
@@ -653,7 +1387,7 @@ impl From<Embed> for MessageResponse {
 impl From<Vec<Embed>> for MessageResponse {
   fn from(e: Vec<Embed>) -> MessageResponse {
     MessageResponse {
-      tts: Some(false),
+      tts: None,
       content: None,
       flags: None,
       embeds: Some(e),
@@ -662,6 +1396,10 @@ impl From<Vec<Embed>> for MessageResponse {
       allowed_mentions: None,
       files: None,
       poll: None,
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: None,
     }
   }
 }
@@ -669,15 +1407,19 @@ impl From<Vec<Embed>> for MessageResponse {
 impl From<Components> for MessageResponse {
   fn from(c: Components) -> MessageResponse {
     MessageResponse {
-      tts: Some(false),
+      tts: None,
       content: None,
       flags: None,
       embeds: None,
-      components: Some(c.0),
+      components: Some(c.into()),
       attachments: None,
       allowed_mentions: None,
       files: None,
       poll: None,
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: None,
     }
   }
 }
@@ -685,7 +1427,7 @@ impl From<Components> for MessageResponse {
 impl From<File> for MessageResponse {
   fn from(f: File) -> MessageResponse {
     MessageResponse {
-      tts: Some(false),
+      tts: None,
       content: None,
       flags: None,
       embeds: None,
@@ -694,6 +1436,10 @@ impl From<File> for MessageResponse {
       allowed_mentions: None,
       files: Some(vec![f]),
       poll: None,
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: None,
     }
   }
 }
@@ -701,7 +1447,7 @@ impl From<File> for MessageResponse {
 impl From<Vec<File>> for MessageResponse {
   fn from(f: Vec<File>) -> MessageResponse {
     MessageResponse {
-      tts: Some(false),
+      tts: None,
       content: None,
       flags: None,
       embeds: None,
@@ -710,6 +1456,10 @@ impl From<Vec<File>> for MessageResponse {
       allowed_mentions: None,
       files: Some(f),
       poll: None,
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: None,
     }
   }
 }
@@ -717,7 +1467,7 @@ impl From<Vec<File>> for MessageResponse {
 impl From<PollCreateRequest> for MessageResponse {
   fn from(poll: PollCreateRequest) -> MessageResponse {
     MessageResponse {
-      tts: Some(false),
+      tts: None,
       content: None,
       flags: None,
       embeds: None,
@@ -726,10 +1476,40 @@ impl From<PollCreateRequest> for MessageResponse {
       allowed_mentions: None,
       files: None,
       poll: Some(poll),
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: None,
+    }
+  }
+}
+
+impl From<MessageReference> for MessageResponse {
+  fn from(message_reference: MessageReference) -> MessageResponse {
+    MessageResponse {
+      tts: None,
+      content: None,
+      flags: None,
+      embeds: None,
+      components: None,
+      attachments: None,
+      allowed_mentions: None,
+      files: None,
+      poll: None,
+      nonce: None,
+      enforce_nonce: None,
+      sticker_ids: None,
+      message_reference: Some(message_reference),
     }
   }
 }
 
+impl From<&RestError> for MessageResponse {
+  fn from(error: &RestError) -> MessageResponse {
+    MessageResponse::from_error(error)
+  }
+}
+
 impl Attachments for MessageResponse {
   fn take_attachments(&mut self) -> Vec<Attachment> {
     self.attachments.take().unwrap_or_default()
@@ -45,11 +45,19 @@
 //! You may now route it through a reverse proxy and set your interaction url on the Developer Portal.
 //!
 //! Take a look at [CommandInput](commands::CommandInput) and [CommandResponder](commands::CommandResponder) for the values and functions you have at your disposal in your functions.
+//!
+//! ## Features
+//! - `server` (default): The Rocket-based webhook listener started with `Client::start`.
+//!   Disable it to embed the library in your own web server with only the interaction parsing, [signature verification](verify_signature), REST and structs.
+//!   Interactions are then passed to [`Client::dispatch`] to run the matching command.
 
 pub(crate) const USER_AGENT: &str = concat!("slashook/", env!("CARGO_PKG_VERSION"));
 
+#[cfg(feature = "server")]
 #[macro_use] extern crate rocket;
+#[cfg(feature = "server")]
 mod webhook;
+mod verification;
 pub mod structs;
 pub mod commands;
 pub mod rest;
@@ -57,22 +65,55 @@ pub mod rest;
 // Macros
 pub use slashook_macros::*;
 
+pub use verification::verify_signature;
+
 // Re-exports
-pub use rocket::{async_main, tokio};
+#[cfg(feature = "server")]
+pub use rocket::async_main;
+pub use tokio;
 pub use chrono;
 
+// Used by `#[slashook::main]` in place of Rocket's runtime when the `server` feature is disabled
+#[cfg(not(feature = "server"))]
+#[doc(hidden)]
+pub fn async_main<T>(fut: impl std::future::Future<Output = T> + Send) -> T {
+  tokio::runtime::Builder::new_multi_thread()
+    .enable_all()
+    .build()
+    .expect("failed to build the async runtime")
+    .block_on(fut)
+}
+
 use std::{
+  collections::BTreeMap,
   net::{IpAddr, Ipv4Addr},
   sync::Arc
 };
+use anyhow::Context;
+#[cfg(feature = "server")]
 use tokio::{sync::mpsc, spawn};
 
-use commands::{Command, handler::{CommandHandler, RocketCommand}};
-use structs::interactions::ApplicationCommand;
+use commands::{AsyncCmdFn, Command, handler::CommandHandler};
+#[cfg(feature = "server")]
+use commands::handler::RocketCommand;
+use structs::{
+  interactions::{ApplicationCommand, IntegrationType, Interaction, InteractionCallback, InteractionCallbackType, InteractionContextType, InteractionType},
+  Snowflake
+};
 use rest::Rest;
 
 /// Configuration options for the client
-#[derive(Clone, Debug)]
+///
+/// The `Debug` output redacts the client secret and bot token so the config can be logged safely.
+/// ```
+/// # use slashook::Config;
+/// let config = Config {
+///   bot_token: Some(String::from("your.bot.token")),
+///   ..Default::default()
+/// };
+/// assert!(!format!("{:?}", config).contains("your.bot.token"));
+/// ```
+#[derive(Clone)]
 pub struct Config {
   /// IP address to bind to
   pub ip: IpAddr,
@@ -85,9 +126,51 @@ pub struct Config {
   /// Client Secret provided by Discord, required for syncing commands without a bot token
   pub client_secret: Option<String>,
   /// Bot token provided by Discord for Bot accounts
-  pub bot_token: Option<String>
+  pub bot_token: Option<String>,
+  /// Identity sent in the `Server` and `User-Agent` headers of responses. Defaults to the library name and version.\
+  /// Set to `None` to omit the headers entirely.
+  pub server_ident: Option<String>,
+  /// Number of worker threads for executing futures. Defaults to the number of CPU cores.\
+  /// The async runtime is created before the client is, so this only takes effect when the runtime is built with [`Config::build_runtime`].
+  /// `#[slashook::main]` reads the `ROCKET_WORKERS` environment variable instead.
+  pub workers: Option<usize>,
+  /// Limit on the number of threads to start for blocking tasks. Defaults to 512.\
+  /// Like `workers`, only takes effect when the runtime is built with [`Config::build_runtime`] or through the `ROCKET_MAX_BLOCKING` environment variable.
+  pub max_blocking: Option<usize>,
+  /// Integration types applied when syncing to every command that doesn't set its own `integration_types`
+  pub default_integration_types: Option<Vec<IntegrationType>>,
+  /// Interaction contexts applied when syncing to every command that doesn't set its own `contexts`
+  pub default_contexts: Option<Vec<InteractionContextType>>,
+  /// Whether to decompress request bodies sent with a `gzip`, `deflate` or `br` `Content-Encoding`. Defaults to `false`.\
+  /// Discord doesn't compress requests, so this is only needed behind a proxy that does.
+  /// The body is decompressed before the signature is checked, which only works if the proxy compresses the exact bytes Discord sent.
+  pub decompress_requests: bool,
+  /// Maximum number of command handlers running at once. Defaults to no limit.\
+  /// Interactions over the limit are queued until a running handler finishes.
+  /// Discord still expects a response within 3 seconds, so queued interactions that wait too long will fail.
+  pub max_concurrent_handlers: Option<usize>,
+  /// Creates the [Rest] handler used for each interaction instead of one made from `bot_token`.\
+  /// The handler is placed in [`CommandInput::rest`](commands::CommandInput::rest) and used by the [CommandResponder](commands::CommandResponder),
+  /// which allows serving multiple bot identities from a single process.
+  /// ```
+  /// # use slashook::{Config, rest::Rest};
+  /// # use std::sync::Arc;
+  /// let config = Config {
+  ///   rest_for_interaction: Some(Arc::new(|interaction| {
+  ///     match interaction.guild_id.as_deref() {
+  ///       Some("613425648685547541") => Rest::with_bot_token("other.bot.token").unwrap(),
+  ///       _ => Rest::with_bot_token("your.bot.token").unwrap()
+  ///     }
+  ///   })),
+  ///   ..Default::default()
+  /// };
+  /// ```
+  pub rest_for_interaction: Option<RestForInteraction>
 }
 
+/// Function creating the [Rest] handler for an interaction, see [`Config::rest_for_interaction`]
+pub type RestForInteraction = Arc<dyn Fn(&Interaction) -> Rest + Send + Sync>;
+
 impl Default for Config {
   fn default() -> Self {
     Self {
@@ -97,22 +180,330 @@ impl Default for Config {
       client_id: None,
       client_secret: None,
       bot_token: None,
+      server_ident: Some(USER_AGENT.to_string()),
+      workers: None,
+      max_blocking: None,
+      default_integration_types: None,
+      default_contexts: None,
+      decompress_requests: false,
+      max_concurrent_handlers: None,
+      rest_for_interaction: None,
+    }
+  }
+}
+
+impl Config {
+  /// Builds a multi-threaded async runtime using the `workers` and `max_blocking` options.\
+  /// Use this instead of `#[slashook::main]` to tune the runtime from code.
+  /// ```no_run
+  /// # use slashook::{Client, Config};
+  /// fn main() -> std::io::Result<()> {
+  ///   let config = Config {
+  ///     workers: Some(16),
+  ///     ..Default::default()
+  ///   };
+  ///   let runtime = config.build_runtime()?;
+  ///   let client = Client::new(config);
+  ///   runtime.block_on(client.start());
+  ///   Ok(())
+  /// }
+  /// ```
+  pub fn build_runtime(&self) -> std::io::Result<tokio::runtime::Runtime> {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all().thread_name("slashook-worker");
+    if let Some(workers) = self.workers {
+      builder.worker_threads(workers);
+    }
+    if let Some(max_blocking) = self.max_blocking {
+      builder.max_blocking_threads(max_blocking);
+    }
+    builder.build()
+  }
+}
+
+impl std::fmt::Debug for Config {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let redact = |value: &Option<String>| value.as_ref().map(|_| "***");
+    f.debug_struct("Config")
+      .field("ip", &self.ip)
+      .field("port", &self.port)
+      .field("public_key", &self.public_key)
+      .field("client_id", &self.client_id)
+      .field("client_secret", &redact(&self.client_secret))
+      .field("bot_token", &redact(&self.bot_token))
+      .field("server_ident", &self.server_ident)
+      .field("workers", &self.workers)
+      .field("max_blocking", &self.max_blocking)
+      .field("default_integration_types", &self.default_integration_types)
+      .field("default_contexts", &self.default_contexts)
+      .field("decompress_requests", &self.decompress_requests)
+      .field("max_concurrent_handlers", &self.max_concurrent_handlers)
+      .field("rest_for_interaction", &self.rest_for_interaction.as_ref().map(|_| "Fn"))
+      .finish()
+  }
+}
+
+/// Errors the webhook listener responds to by itself
+#[cfg(feature = "server")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WebhookError {
+  /// The request wasn't signed by Discord
+  BadSignature,
+  /// The request body couldn't be read as an interaction
+  BadBody,
+  /// The request body was compressed with an unsupported encoding
+  UnsupportedEncoding,
+  /// The interaction type isn't supported
+  UnknownInteractionType,
+  /// The requested route doesn't exist
+  NotFound,
+  /// The command handler failed to produce a response
+  HandlerFailed,
+  /// The command handler responded with a message that breaks Discord's limits
+  InvalidResponse,
+  /// The command handler isn't running
+  HandlerUnavailable,
+  /// Any other error
+  Unexpected
+}
+
+/// Status and body sent for a [WebhookError]
+#[cfg(feature = "server")]
+#[derive(Clone, Debug)]
+pub struct ErrorResponse {
+  /// HTTP status code
+  pub status: u16,
+  /// JSON body, `None` for an empty body
+  pub body: Option<serde_json::Value>
+}
+
+#[cfg(feature = "server")]
+impl WebhookError {
+  /// Returns the response sent for the error when no custom [error responder](Client::set_error_responder) is set
+  /// ```
+  /// # use slashook::WebhookError;
+  /// let response = WebhookError::BadSignature.default_response();
+  /// assert_eq!(response.status, 401);
+  /// assert_eq!(response.body.unwrap()["error"], "Bad signature");
+  /// ```
+  pub fn default_response(&self) -> ErrorResponse {
+    let (status, error) = match self {
+      Self::BadSignature => (401, "Bad signature"),
+      Self::BadBody => (400, "Bad body"),
+      Self::UnsupportedEncoding => (415, "Unsupported content encoding"),
+      Self::UnknownInteractionType => (404, "Unknown interaction type"),
+      Self::NotFound => (404, "Not found"),
+      Self::HandlerFailed => (500, "Handler failed"),
+      Self::InvalidResponse => (500, "Invalid response"),
+      Self::HandlerUnavailable => (503, "Handler unavailable"),
+      Self::Unexpected => (500, "Unexpected error")
+    };
+    ErrorResponse {
+      status,
+      body: Some(serde_json::json!({ "error": error }))
+    }
+  }
+}
+
+#[cfg(feature = "server")]
+pub(crate) type ErrorResponder = Arc<dyn Fn(WebhookError) -> ErrorResponse + Send + Sync>;
+
+/// The changes made by syncing commands, see [`Client::sync_commands_detailed`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandSyncSummary {
+  /// Names of the commands that didn't exist before
+  pub created: Vec<String>,
+  /// Names of the commands that existed and were changed
+  pub updated: Vec<String>,
+  /// Names of the commands that were removed
+  pub deleted: Vec<String>
+}
+
+impl CommandSyncSummary {
+  /// Compares the commands before and after a sync. Commands are matched by name and type
+  /// and a command counts as updated when Discord gave it a new version.
+  /// ```
+  /// # use slashook::CommandSyncSummary;
+  /// # use slashook::structs::interactions::ApplicationCommand;
+  /// let command = |name: &str, version: &str| ApplicationCommand {
+  ///   name: String::from(name),
+  ///   version: Some(String::from(version)),
+  ///   ..Default::default()
+  /// };
+  /// let before = vec![command("ping", "1"), command("echo", "1"), command("old", "1")];
+  /// let after = vec![command("ping", "1"), command("echo", "2"), command("new", "1")];
+  /// let summary = CommandSyncSummary::between(&before, &after);
+  /// assert_eq!(summary.created, vec!["new"]);
+  /// assert_eq!(summary.updated, vec!["echo"]);
+  /// assert_eq!(summary.deleted, vec!["old"]);
+  /// ```
+  pub fn between(before: &[ApplicationCommand], after: &[ApplicationCommand]) -> Self {
+    let same_command = |a: &ApplicationCommand, b: &ApplicationCommand| a.name == b.name && a.command_type == b.command_type;
+    let mut summary = Self::default();
+
+    for command in after.iter() {
+      match before.iter().find(|old| same_command(old, command)) {
+        None => summary.created.push(command.name.clone()),
+        Some(old) if old.version != command.version => summary.updated.push(command.name.clone()),
+        Some(_) => {}
+      }
     }
+
+    for command in before.iter() {
+      if !after.iter().any(|new| same_command(new, command)) {
+        summary.deleted.push(command.name.clone());
+      }
+    }
+
+    summary
+  }
+
+  /// Returns whether the sync didn't change anything
+  pub fn is_empty(&self) -> bool {
+    self.created.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
   }
 }
 
 /// The entry point of the library
 pub struct Client {
   config: Config,
-  command_handler: CommandHandler
+  command_handler: CommandHandler,
+  #[cfg(feature = "server")]
+  sync_on_start: bool,
+  #[cfg(feature = "server")]
+  error_responder: Option<ErrorResponder>
+}
+
+/// A fluent builder for a [Client]
+///
+/// ```no_run
+/// #[macro_use] extern crate slashook;
+/// use slashook::Client;
+/// use slashook::commands::{ CommandInput, CommandResponder };
+///
+/// #[slashook::main]
+/// async fn main() {
+///   #[command(name = "ping", description = "pong")]
+///   fn ping(input: CommandInput, res: CommandResponder) {
+///     res.send_message("Pong!").await?;
+///   }
+///
+///   Client::builder()
+///     .public_key("your_public_key")
+///     .client_id("your_client_id")
+///     .bot_token("your.bot.token")
+///     .command(ping)
+///     .sync_on_start(true)
+///     .build()
+///     .start()
+///     .await;
+/// }
+/// ```
+pub struct ClientBuilder {
+  client: Client
+}
+
+impl ClientBuilder {
+  /// Replaces the whole configuration. Options set before this are overwritten.
+  pub fn config(mut self, config: Config) -> Self {
+    self.client.command_handler.set_concurrency_limit(config.max_concurrent_handlers);
+    self.client.config = config;
+    self
+  }
+
+  /// Sets the public key
+  pub fn public_key<T: ToString>(mut self, public_key: T) -> Self {
+    self.client.config.public_key = public_key.to_string();
+    self
+  }
+
+  /// Sets the client id
+  pub fn client_id<T: ToString>(mut self, client_id: T) -> Self {
+    self.client.config.client_id = Some(client_id.to_string());
+    self
+  }
+
+  /// Sets the client secret
+  pub fn client_secret<T: ToString>(mut self, client_secret: T) -> Self {
+    self.client.config.client_secret = Some(client_secret.to_string());
+    self
+  }
+
+  /// Sets the bot token
+  pub fn bot_token<T: ToString>(mut self, bot_token: T) -> Self {
+    self.client.config.bot_token = Some(bot_token.to_string());
+    self
+  }
+
+  /// Sets the IP address to bind to
+  pub fn ip(mut self, ip: IpAddr) -> Self {
+    self.client.config.ip = ip;
+    self
+  }
+
+  /// Sets the port to listen to
+  pub fn port(mut self, port: u16) -> Self {
+    self.client.config.port = port;
+    self
+  }
+
+  /// Registers a command. See [`Client::register_command`]
+  pub fn command(mut self, command: Command) -> Self {
+    self.client.register_command(command);
+    self
+  }
+
+  /// Registers multiple commands. See [`Client::register_commands`]
+  pub fn commands(mut self, commands: Vec<Command>) -> Self {
+    self.client.register_commands(commands);
+    self
+  }
+
+  /// Adds a value to the shared state. See [`Client::manage`]
+  pub fn manage<T: Send + Sync + 'static>(mut self, state: T) -> Self {
+    self.client.manage(state);
+    self
+  }
+
+  /// Sets a custom error responder. See [`Client::set_error_responder`]
+  #[cfg(feature = "server")]
+  pub fn error_responder<F: Fn(WebhookError) -> ErrorResponse + Send + Sync + 'static>(mut self, responder: F) -> Self {
+    self.client.set_error_responder(responder);
+    self
+  }
+
+  /// Sets whether [`start`](Client::start) syncs the commands with [`sync_all_commands`](Client::sync_all_commands) before listening. Defaults to `false`.
+  #[cfg(feature = "server")]
+  pub fn sync_on_start(mut self, sync_on_start: bool) -> Self {
+    self.client.sync_on_start = sync_on_start;
+    self
+  }
+
+  /// Returns the configured client
+  pub fn build(self) -> Client {
+    self.client
+  }
 }
 
 impl Client {
   /// Creates a new client with the configuration provided
   pub fn new(config: Config) -> Self {
+    let mut command_handler = CommandHandler::new();
+    command_handler.set_concurrency_limit(config.max_concurrent_handlers);
     Self {
       config,
-      command_handler: CommandHandler::new()
+      command_handler,
+      #[cfg(feature = "server")]
+      sync_on_start: false,
+      #[cfg(feature = "server")]
+      error_responder: None
+    }
+  }
+
+  /// Creates a [ClientBuilder] starting from the default configuration
+  pub fn builder() -> ClientBuilder {
+    ClientBuilder {
+      client: Self::new(Config::default())
     }
   }
 
@@ -158,11 +549,99 @@ impl Client {
     self
   }
 
+  /// Registers a handler for message components whose custom_id starts with `name/`.\
+  /// Component handlers are kept apart from commands and never synced, so they can't collide with command names.
+  /// Components without a matching handler fall back to the command with that name.
+  ///
+  /// ```
+  /// # use slashook::{Client, Config, commands::{CmdResult, CommandInput, CommandResponder}};
+  /// # let config = Config::default();
+  /// # let mut client = Client::new(config);
+  /// async fn delete_button(input: CommandInput, res: CommandResponder) -> CmdResult {
+  ///   // Handles buttons with custom_ids like `delete/1234`
+  ///   res.update_message(format!("Deleted {}", input.custom_id.unwrap())).await?;
+  ///   Ok(())
+  /// }
+  /// client.register_component_handler("delete", delete_button);
+  /// ```
+  pub fn register_component_handler<T: ToString, F: AsyncCmdFn + 'static>(&mut self, name: T, handler: F) -> &mut Self {
+    self.command_handler.add_component_handler(name.to_string(), handler);
+    self
+  }
+
+  /// Registers a handler for modals whose custom_id starts with `name/`.\
+  /// Works the same as [`register_component_handler`](Client::register_component_handler) but for modal submits.
+  ///
+  /// ```
+  /// # use slashook::{Client, Config, commands::{CmdResult, CommandInput, CommandResponder}};
+  /// # let config = Config::default();
+  /// # let mut client = Client::new(config);
+  /// async fn feedback_modal(input: CommandInput, res: CommandResponder) -> CmdResult {
+  ///   let feedback = input.args.get("feedback").and_then(|v| v.as_string()).unwrap_or_default();
+  ///   res.send_message(format!("Thanks for the feedback: {}", feedback)).await?;
+  ///   Ok(())
+  /// }
+  /// client.register_modal_handler("feedback", feedback_modal);
+  /// ```
+  pub fn register_modal_handler<T: ToString, F: AsyncCmdFn + 'static>(&mut self, name: T, handler: F) -> &mut Self {
+    self.command_handler.add_modal_handler(name.to_string(), handler);
+    self
+  }
+
+  /// Adds a value to the shared state accessible from every command with [`CommandInput::state`](commands::CommandInput::state).\
+  /// Only one value of each type can be managed, managing another value of the same type replaces the previous one.
+  ///
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::{Client, Config, commands::{CommandInput, CommandResponder}};
+  /// # use std::sync::atomic::{AtomicUsize, Ordering};
+  /// # let config = Config::default();
+  /// # let mut client = Client::new(config);
+  /// struct HitCount(AtomicUsize);
+  ///
+  /// ##[command(name = "count", description = "Counts how many times this command has been used")]
+  /// fn count(input: CommandInput, res: CommandResponder) {
+  ///   let hits = input.state::<HitCount>().unwrap();
+  ///   let count = hits.0.fetch_add(1, Ordering::Relaxed) + 1;
+  ///   res.send_message(format!("This command has been used {} times", count)).await?;
+  /// }
+  /// client.manage(HitCount(AtomicUsize::new(0)));
+  /// client.register_command(count);
+  /// ```
+  pub fn manage<T: Send + Sync + 'static>(&mut self, state: T) -> &mut Self {
+    self.command_handler.manage(state);
+    self
+  }
+
+  /// Sets a function that produces the status and body for errors the webhook listener responds to by itself.\
+  /// Use [`WebhookError::default_response`] to keep the default for some errors.
+  ///
+  /// ```
+  /// # use slashook::{Client, Config, WebhookError, ErrorResponse};
+  /// # let config = Config::default();
+  /// # let mut client = Client::new(config);
+  /// client.set_error_responder(|error| match error {
+  ///   WebhookError::NotFound => ErrorResponse { status: 404, body: None },
+  ///   error => {
+  ///     let default = error.default_response();
+  ///     ErrorResponse {
+  ///       body: Some(serde_json::json!({ "ok": false, "code": default.status })),
+  ///       ..default
+  ///     }
+  ///   }
+  /// });
+  /// ```
+  #[cfg(feature = "server")]
+  pub fn set_error_responder<F: Fn(WebhookError) -> ErrorResponse + Send + Sync + 'static>(&mut self, responder: F) -> &mut Self {
+    self.error_responder = Some(Arc::new(responder));
+    self
+  }
+
   async fn create_sync_rest(&self) -> anyhow::Result<Rest> {
     let rest;
 
     if let Some(bot_token) = &self.config.bot_token {
-      rest = Rest::with_token(bot_token.to_string());
+      rest = Rest::with_token(bot_token.to_string())?;
     } else {
       if self.config.client_secret.is_none() {
         anyhow::bail!("A client_secret or bot_token is required in the config to sync commands");
@@ -177,8 +656,59 @@ impl Client {
     Ok(rest)
   }
 
+  fn convert_all_commands(&self) -> anyhow::Result<Vec<(ApplicationCommand, Option<Vec<Snowflake>>)>> {
+    let mut commands = self.command_handler.convert_commands()?;
+    for (command, guild_ids) in commands.iter_mut() {
+      // Installation contexts only apply to global commands
+      if guild_ids.is_some() { continue }
+      if command.integration_types.is_none() {
+        command.integration_types.clone_from(&self.config.default_integration_types);
+      }
+      if command.contexts.is_none() {
+        command.contexts.clone_from(&self.config.default_contexts);
+      }
+    }
+    Ok(commands)
+  }
+
+  fn convert_commands(&self) -> anyhow::Result<Vec<ApplicationCommand>> {
+    let commands = self.convert_all_commands()?;
+    Ok(commands.into_iter().filter(|(_, guild_ids)| guild_ids.is_none()).map(|(command, _)| command).collect())
+  }
+
+  /// Returns the commands exactly as they would be sent by [`sync_commands`](Client::sync_commands) without contacting Discord
+  ///
+  /// Commands with `guild_ids` aren't included since they're only synced by [`sync_all_commands`](Client::sync_all_commands).
+  ///
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::{Client, Config, commands::{CommandInput, CommandResponder}};
+  /// # use slashook::structs::interactions::IntegrationType;
+  /// let config = Config {
+  ///   default_integration_types: Some(vec![IntegrationType::GUILD_INSTALL]),
+  ///   ..Default::default()
+  /// };
+  /// let mut client = Client::new(config);
+  /// ##[command(name = "command", description = "An example command")]
+  /// fn command(_: CommandInput, res: CommandResponder) {
+  ///   res.send_message("Response");
+  /// }
+  /// client.register_command(command);
+  /// let commands = client.preview_commands().unwrap();
+  /// assert_eq!(commands[0].name, "command");
+  /// assert!(commands[0].integration_types.is_some());
+  /// println!("{}", serde_json::to_string_pretty(&commands).unwrap());
+  /// ```
+  pub fn preview_commands(&self) -> anyhow::Result<Vec<ApplicationCommand>> {
+    self.convert_commands()
+  }
+
   /// Syncs defined commands with Discord
   ///
+  /// If any of the commands are invalid, nothing is synced and the returned error lists every failing command by name.\
+  /// Commands without their own `integration_types` or `contexts` use the defaults from the [Config].
+  /// Commands with `guild_ids` are skipped, use [`sync_all_commands`](Client::sync_all_commands) to sync them to their guilds.
+  ///
   /// ```
   /// # #[macro_use] extern crate slashook;
   /// # use slashook::{Client, Config, commands::{CommandInput, CommandResponder}};
@@ -200,13 +730,49 @@ impl Client {
     }
 
     let rest = self.create_sync_rest().await?;
-    let commands = self.command_handler.convert_commands()?;
+    let commands = self.convert_commands()?;
 
     Ok(ApplicationCommand::bulk_overwrite_global_commands(&rest, self.config.client_id.as_ref().unwrap(), commands).await?)
   }
 
+  /// Syncs defined commands with Discord like [`sync_commands`](Client::sync_commands) and reports what changed
+  ///
+  /// The current commands are fetched before overwriting them to compare against.
+  /// ```no_run
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::{Client, Config, commands::{CommandInput, CommandResponder}};
+  /// # #[slashook::main]
+  /// # async fn main() -> anyhow::Result<()> {
+  /// # let config = Config::default();
+  /// # let mut client = Client::new(config);
+  /// ##[command(name = "command", description = "An example command")]
+  /// fn command(_: CommandInput, res: CommandResponder) {
+  ///   res.send_message("Response");
+  /// }
+  /// client.register_command(command);
+  /// let (_, summary) = client.sync_commands_detailed().await?;
+  /// println!("Created: {:?}, updated: {:?}, deleted: {:?}", summary.created, summary.updated, summary.deleted);
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn sync_commands_detailed(&self) -> anyhow::Result<(Vec<ApplicationCommand>, CommandSyncSummary)> {
+    if self.config.client_id.is_none() {
+      anyhow::bail!("A client_id is required in the config to sync commands");
+    }
+
+    let client_id = self.config.client_id.as_ref().unwrap();
+    let rest = self.create_sync_rest().await?;
+    let commands = self.convert_commands()?;
+
+    let before = ApplicationCommand::fetch_global_commands(&rest, client_id).await?;
+    let after = ApplicationCommand::bulk_overwrite_global_commands(&rest, client_id, commands).await?;
+    let summary = CommandSyncSummary::between(&before, &after);
+    Ok((after, summary))
+  }
+
   /// Syncs defined commands with Discord as guild commands
   ///
+  /// The same commands as [`sync_commands`](Client::sync_commands) are synced, which is useful for testing global commands in a single guild.
   /// ```
   /// # #[macro_use] extern crate slashook;
   /// # use slashook::{Client, Config, commands::{CommandInput, CommandResponder}};
@@ -228,15 +794,114 @@ impl Client {
     }
 
     let rest = self.create_sync_rest().await?;
-    let commands = self.command_handler.convert_commands()?;
+    let commands = self.convert_commands()?;
 
     Ok(ApplicationCommand::bulk_overwrite_guild_commands(&rest, self.config.client_id.as_ref().unwrap(), guild_id, commands).await?)
   }
 
-  /// Starts the webhook listener, setting everything into motion
+  /// Syncs commands without `guild_ids` globally and commands with `guild_ids` to each of their guilds
+  ///
+  /// Every guild that has at least one command gets its guild commands overwritten with the commands for it.
+  /// Guilds that no longer have any commands aren't touched, use [`ApplicationCommand::bulk_overwrite_guild_commands`] with an empty list to clear them.\
+  /// Returns all the synced commands, the guild commands have their `guild_id` set.
+  /// ```no_run
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::{Client, Config, commands::{CommandInput, CommandResponder}};
+  /// # #[slashook::main]
+  /// # async fn main() -> anyhow::Result<()> {
+  /// # let config = Config::default();
+  /// # let mut client = Client::new(config);
+  /// ##[command(name = "ping", description = "Available everywhere")]
+  /// fn ping(_: CommandInput, res: CommandResponder) {
+  ///   res.send_message("Pong!").await?;
+  /// }
+  /// ##[command(name = "premium", description = "Only for premium servers", guild_ids = ["613425648685547541", "344581372137963522"])]
+  /// fn premium(_: CommandInput, res: CommandResponder) {
+  ///   res.send_message("Thanks for being premium!").await?;
+  /// }
+  /// client.register_command(ping);
+  /// client.register_command(premium);
+  /// assert_eq!(client.preview_commands()?.len(), 1);
+  /// client.sync_all_commands().await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn sync_all_commands(&self) -> anyhow::Result<Vec<ApplicationCommand>> {
+    if self.config.client_id.is_none() {
+      anyhow::bail!("A client_id is required in the config to sync commands");
+    }
+
+    let client_id = self.config.client_id.as_ref().unwrap();
+    let rest = self.create_sync_rest().await?;
+
+    let mut global_commands = Vec::new();
+    let mut guild_commands: BTreeMap<Snowflake, Vec<ApplicationCommand>> = BTreeMap::new();
+    for (command, guild_ids) in self.convert_all_commands()? {
+      match guild_ids {
+        Some(guild_ids) => {
+          for guild_id in guild_ids {
+            guild_commands.entry(guild_id).or_default().push(command.clone());
+          }
+        },
+        None => global_commands.push(command)
+      }
+    }
+
+    let mut synced = ApplicationCommand::bulk_overwrite_global_commands(&rest, client_id, global_commands).await?;
+    for (guild_id, commands) in guild_commands {
+      let guild_synced = ApplicationCommand::bulk_overwrite_guild_commands(&rest, client_id, &guild_id, commands).await
+        .with_context(|| format!("Failed to sync commands for guild {}", guild_id))?;
+      synced.extend(guild_synced);
+    }
+
+    Ok(synced)
+  }
+
+  /// Runs the registered command matching an interaction and returns the initial response for Discord
+  ///
+  /// This is what the webhook listener does for every interaction, for when you receive and [verify](verify_signature) interactions in your own web server.
+  /// Pings are answered with a pong without running any command.
+  ///
+  /// Must be called within a Tokio runtime since the command runs in a spawned task.
+  /// The returned future resolves once the command sends its initial response and the command keeps running in the background after that,
+  /// so the response should be sent back to Discord right away. Discord waits for it for 3 seconds.\
+  /// The response is serialized as JSON, unless its `data` has `files`, which need to be sent as `multipart/form-data` with the JSON in a `payload_json` field.
+  /// The client can be shared between tasks in an [Arc] to dispatch interactions concurrently.
+  /// ```no_run
+  /// # use slashook::{Client, Config, structs::interactions::Interaction};
+  /// # #[slashook::main]
+  /// # async fn main() -> anyhow::Result<()> {
+  /// # let client = Client::new(Config::default());
+  /// # let body: &[u8] = b"";
+  /// let interaction: Interaction = serde_json::from_slice(body)?;
+  /// let response = client.dispatch(interaction).await?;
+  /// let json = serde_json::to_string(&response)?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn dispatch(&self, interaction: Interaction) -> anyhow::Result<InteractionCallback> {
+    match interaction.interaction_type {
+      InteractionType::PING => return Ok(InteractionCallback { response_type: InteractionCallbackType::PONG, data: None }),
+      InteractionType::UNKNOWN => anyhow::bail!("Unknown interaction type"),
+      _ => ()
+    }
+    let rest = self.config.rest_for_interaction.as_ref().map(|rest_for_interaction| rest_for_interaction(&interaction));
+    self.command_handler.handle_command(interaction, self.config.bot_token.clone(), rest).await
+  }
+
+  /// Starts the webhook listener, setting everything into motion\
+  /// If the client was built with [`sync_on_start`](ClientBuilder::sync_on_start), the commands are synced with [`sync_all_commands`](Client::sync_all_commands) first.
+  /// A failed sync is logged and the listener is started anyway.
+  #[cfg(feature = "server")]
   pub async fn start(self) {
+    if self.sync_on_start {
+      if let Err(err) = self.sync_all_commands().await {
+        eprintln!("Failed to sync commands on start: {:#}", err);
+      }
+    }
+
     let (sender, receiver) = mpsc::unbounded_channel::<RocketCommand>();
-    let rocket = webhook::start(self.config, sender);
+    let rocket = webhook::start(self.config, sender, self.error_responder);
 
     let command_handler = Arc::new(self.command_handler);
     spawn(async move {
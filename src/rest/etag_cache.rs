@@ -0,0 +1,51 @@
+// Copyright 2024 slashook Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::{HashMap, VecDeque};
+
+pub(crate) struct CachedResponse {
+  pub etag: String,
+  pub body: String
+}
+
+/// Bounded cache of response bodies keyed by url, evicting the oldest entry when full
+pub(crate) struct EtagCache {
+  capacity: usize,
+  entries: HashMap<String, CachedResponse>,
+  order: VecDeque<String>
+}
+
+impl EtagCache {
+  pub fn new(capacity: usize) -> Self {
+    Self {
+      capacity,
+      entries: HashMap::new(),
+      order: VecDeque::new()
+    }
+  }
+
+  pub fn get(&self, url: &str) -> Option<&CachedResponse> {
+    self.entries.get(url)
+  }
+
+  pub fn insert(&mut self, url: String, response: CachedResponse) {
+    if self.capacity == 0 { return }
+    if self.entries.insert(url.clone(), response).is_some() { return }
+    self.order.push_back(url);
+    while self.order.len() > self.capacity {
+      if let Some(oldest) = self.order.pop_front() {
+        self.entries.remove(&oldest);
+      }
+    }
+  }
+
+  pub fn remove(&mut self, url: &str) {
+    if self.entries.remove(url).is_some() {
+      self.order.retain(|u| u != url);
+    }
+  }
+}
@@ -9,22 +9,35 @@
 
 /// Discord API base URL
 pub const API_URL: &str = "https://discord.com/api/v10";
+const AUDIT_LOG_REASON: &str = "X-Audit-Log-Reason";
+/// Largest file [`Rest::download`] accepts, in bytes. Matches the largest upload Discord allows for bots.
+pub const DOWNLOAD_SIZE_LIMIT: u64 = 100 * 1024 * 1024;
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
 
-use std::any::TypeId;
+mod etag_cache;
+
+use std::{
+  any::TypeId,
+  sync::{Arc, Mutex},
+  time::Duration
+};
+use etag_cache::{EtagCache, CachedResponse};
 use serde::{Serialize, de::{DeserializeOwned, Error}};
 use serde_json::{Value, json};
 use crate::structs::{
   messages::Attachment,
   interactions::Attachments,
-  utils::File
+  utils::{File, ValidationError}
 };
+pub use reqwest::{Method, StatusCode, header::HeaderMap};
+pub use bytes::Bytes;
 use reqwest::{
   Client,
   ClientBuilder,
-  StatusCode,
+  RequestBuilder,
   Response,
   multipart::{Form, Part},
-  header::{HeaderMap, HeaderValue}
+  header::{HeaderValue, ETAG, IF_NONE_MATCH}
 };
 use thiserror::Error;
 
@@ -37,6 +50,36 @@ pub enum RestError {
   /// Represents an error that occurred within the serde library
   #[error("Failed to (de)serialize data")]
   SerializationError(#[from] serde_json::Error),
+  /// Represents an error in deserializing a successful response
+  #[error("Failed to deserialize response into {target_type}: {source}")]
+  DeserializeError {
+    /// The error from serde
+    source: serde_json::Error,
+    /// Body of the response
+    body: String,
+    /// Name of the type the body was being deserialized into
+    target_type: &'static str
+  },
+  /// Represents a successful response without a body when the expected type needs one
+  #[error("Response with status {status} had no body to deserialize into {target_type}")]
+  EmptyResponse {
+    /// Status code of the response
+    status: StatusCode,
+    /// Name of the type the body was being deserialized into
+    target_type: &'static str
+  },
+  /// Represents a download that was stopped for being larger than the limit
+  #[error("The download was larger than the limit of {limit} bytes")]
+  DownloadTooLarge {
+    /// The limit in bytes
+    limit: u64
+  },
+  /// Represents a token that can't be used in the `Authorization` header
+  #[error("The provided token is not a valid header value")]
+  InvalidToken(#[from] reqwest::header::InvalidHeaderValue),
+  /// Represents a request that was rejected before being sent because it would be invalid
+  #[error(transparent)]
+  ValidationError(#[from] ValidationError),
   /// Represents an error for requests with a failed status
   #[error("Request failed with status {status}. Body: {body}")]
   RequestFailed {
@@ -47,13 +90,36 @@ pub enum RestError {
   }
 }
 
+impl RestError {
+  /// Returns the [JSON error code](https://discord.com/developers/docs/topics/opcodes-and-status-codes#json) Discord sent with a failed request, if any
+  /// ```
+  /// # use slashook::rest::RestError;
+  /// let err = RestError::RequestFailed {
+  ///   status: reqwest::StatusCode::NOT_FOUND,
+  ///   body: String::from(r#"{"message": "Unknown Ban", "code": 10026}"#)
+  /// };
+  /// assert_eq!(err.error_code(), Some(10026));
+  /// ```
+  pub fn error_code(&self) -> Option<i64> {
+    let RestError::RequestFailed { body, .. } = self else { return None };
+    serde_json::from_str::<Value>(body).ok()?.get("code")?.as_i64()
+  }
+}
+
 /// Handler for Discord API calls
 #[derive(Clone, Default)]
 pub struct Rest {
-  client: Client
+  client: Client,
+  // Client without the authorization header for requests outside the API
+  cdn_client: Client,
+  etag_cache: Option<Arc<Mutex<EtagCache>>>
 }
 
-async fn handle_response<T: DeserializeOwned + 'static>(res: Response) -> Result<T, RestError> {
+/// Turns a response into the expected type, handling errors the same way as the rest of the library.\
+/// Failed statuses become [RequestFailed](RestError::RequestFailed) and responses that can't be deserialized become [DeserializeError](RestError::DeserializeError).
+/// An empty body, such as from a `204 No Content`, deserializes as `null` if the type allows it and becomes [EmptyResponse](RestError::EmptyResponse) otherwise.
+/// Use `()` as the type to ignore the body.
+pub async fn handle_response<T: DeserializeOwned + 'static>(res: Response) -> Result<T, RestError> {
   let status = res.status();
   if status.is_client_error() || status.is_server_error() {
     let body = res.text().await?;
@@ -62,21 +128,45 @@ async fn handle_response<T: DeserializeOwned + 'static>(res: Response) -> Result
   if TypeId::of::<T>() == TypeId::of::<()>() {
     return Ok(serde_json::from_value(Value::Null)?)
   };
-  let body = res.json::<T>().await?;
-  Ok(body)
+  let body = res.text().await?;
+  if body.trim().is_empty() {
+    return serde_json::from_value(Value::Null).map_err(|_| RestError::EmptyResponse {
+      status,
+      target_type: std::any::type_name::<T>()
+    })
+  }
+  deserialize_body(body)
+}
+
+fn deserialize_body<T: DeserializeOwned>(body: String) -> Result<T, RestError> {
+  serde_json::from_str::<T>(&body).map_err(|source| RestError::DeserializeError {
+    source,
+    body,
+    target_type: std::any::type_name::<T>()
+  })
+}
+
+// Discord expects the reason to be URL encoded so it can contain non-ASCII characters
+fn encode_reason(reason: &str) -> String {
+  reason.bytes().map(|b| match b {
+    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+    _ => format!("%{:02X}", b)
+  }).collect()
 }
 
 fn handle_multipart<U: Serialize + Attachments>(mut json_data: U, files: Vec<File>) -> Result<Form, RestError> {
   let mut form_data = Form::new();
-  let mut attachments = json_data.take_attachments();
+  let attachments = Attachment::for_upload(json_data.take_attachments(), &files);
+  json_data.set_attachments(attachments);
 
   for (i, file) in files.into_iter().enumerate() {
-    attachments.push(Attachment::from_file(i.to_string(), &file));
-    let part = Part::bytes(file.data).file_name(file.filename);
+    let mut part = Part::bytes(file.data).file_name(file.filename);
+    if let Some(content_type) = &file.content_type {
+      part = part.mime_str(content_type)?;
+    }
     form_data = form_data.part(format!("files[{}]", i), part);
   }
 
-  json_data.set_attachments(attachments);
   form_data = form_data.text("payload_json", serde_json::to_string(&json_data)?);
   Ok(form_data)
 }
@@ -87,35 +177,65 @@ impl Rest {
       .user_agent(crate::USER_AGENT)
   }
 
+  fn cdn_client_builder() -> ClientBuilder {
+    Self::base_client_builder()
+      .timeout(DOWNLOAD_TIMEOUT)
+  }
+
   /// Creates a new Rest handler without a token
   pub fn new() -> Self {
-    Self::with_optional_token(None)
+    Self {
+      client: Self::base_client_builder().build().unwrap(),
+      cdn_client: Self::cdn_client_builder().build().unwrap(),
+      etag_cache: None
+    }
   }
 
-  /// Creates a new Rest handler with a token
-  pub fn with_token(token: String) -> Self {
-    Self::with_optional_token(Some(token))
+  /// Creates a new Rest handler with a token.\
+  /// Tokens starting with `Bot ` or `Bearer ` are used as is, anything else is treated as a bot token.
+  /// Use [`with_bot_token`](Rest::with_bot_token) or [`with_bearer_token`](Rest::with_bearer_token) to be explicit.
+  /// ```
+  /// # use slashook::rest::{Rest, RestError};
+  /// assert!(Rest::with_token(String::from("Bot abc.def.ghi")).is_ok());
+  /// assert!(matches!(Rest::with_token(String::from("bad\ntoken")), Err(RestError::InvalidToken(_))));
+  /// ```
+  pub fn with_token(token: String) -> Result<Self, RestError> {
+    if token.starts_with("Bot ") || token.starts_with("Bearer ") {
+      Self::with_authorization(token)
+    } else {
+      Self::with_bot_token(token)
+    }
   }
 
-  /// Creates a new Rest handler with or without a token
-  pub fn with_optional_token(token: Option<String>) -> Self {
-    let mut client = Self::base_client_builder();
+  /// Creates a new Rest handler with or without a token. See [`with_token`](Rest::with_token) for how the token is handled.
+  pub fn with_optional_token(token: Option<String>) -> Result<Self, RestError> {
+    match token {
+      Some(token) => Self::with_token(token),
+      None => Ok(Self::new())
+    }
+  }
 
-    if let Some(mut token) = token {
-      if !token.starts_with("Bot") && !token.starts_with("Bearer") {
-        token = format!("Bot {}", token);
-      }
+  /// Creates a new Rest handler with a bot token. The token should not include the `Bot` prefix.
+  pub fn with_bot_token<T: ToString>(token: T) -> Result<Self, RestError> {
+    Self::with_authorization(format!("Bot {}", token.to_string()))
+  }
 
-      let mut headers = HeaderMap::new();
-      let mut auth = HeaderValue::from_str(token.as_str()).unwrap();
-      auth.set_sensitive(true);
-      headers.insert("Authorization", auth);
-      client = client.default_headers(headers);
-    }
+  /// Creates a new Rest handler with an OAuth2 access token. The token should not include the `Bearer` prefix.
+  pub fn with_bearer_token<T: ToString>(token: T) -> Result<Self, RestError> {
+    Self::with_authorization(format!("Bearer {}", token.to_string()))
+  }
 
-    Self {
-      client: client.build().unwrap()
-    }
+  fn with_authorization(authorization: String) -> Result<Self, RestError> {
+    let mut headers = HeaderMap::new();
+    let mut auth = HeaderValue::from_str(authorization.as_str())?;
+    auth.set_sensitive(true);
+    headers.insert("Authorization", auth);
+
+    Ok(Self {
+      client: Self::base_client_builder().default_headers(headers).build()?,
+      cdn_client: Self::cdn_client_builder().build()?,
+      etag_cache: None
+    })
   }
 
   /// Creates a new Rest handler with an access token from client credentials grant
@@ -135,22 +255,154 @@ impl Rest {
       .ok_or_else(|| serde_json::Error::missing_field("access_token"))?.as_str()
       .ok_or_else(|| serde_json::Error::custom("access_token was not a string"))?;
 
-    Ok(Self::with_token(format!("Bearer {}", token)))
+    Self::with_bearer_token(token)
+  }
+
+  /// Creates a request to any path of the API with the authorization already set.\
+  /// This is an escape hatch for endpoints the library doesn't support yet. Send the request yourself and pass the response to [handle_response].
+  /// ```no_run
+  /// # use slashook::rest::{Rest, Method, handle_response};
+  /// # use serde_json::Value;
+  /// # #[slashook::main]
+  /// # async fn main() -> Result<(), slashook::rest::RestError> {
+  /// let rest = Rest::with_bot_token("your.bot.token")?;
+  /// let res = rest.request(Method::GET, "applications/@me")
+  ///   .query(&[("with_counts", true)])
+  ///   .send()
+  ///   .await?;
+  /// let application: Value = handle_response(res).await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn request<T: AsRef<str>>(&self, method: Method, path: T) -> RequestBuilder {
+    self.client.request(method, format!("{}/{}", API_URL, path.as_ref()))
+  }
+
+  /// Downloads a file from outside the API, such as an attachment from Discord's CDN.\
+  /// The authorization header is never sent with these requests.
+  /// The download fails if it takes longer than 30 seconds or the file is larger than [`DOWNLOAD_SIZE_LIMIT`], see [`download_with_limit`](Rest::download_with_limit) for other sizes.
+  pub async fn download<T: AsRef<str>>(&self, url: T) -> Result<Vec<u8>, RestError> {
+    self.download_with_limit(url, DOWNLOAD_SIZE_LIMIT).await
+  }
+
+  /// Downloads a file like [`download`](Rest::download), failing with [DownloadTooLarge](RestError::DownloadTooLarge) if it's larger than `limit` bytes
+  pub async fn download_with_limit<T: AsRef<str>>(&self, url: T, limit: u64) -> Result<Vec<u8>, RestError> {
+    let mut res = self.cdn_client.get(url.as_ref()).send().await?;
+    let status = res.status();
+    if status.is_client_error() || status.is_server_error() {
+      let body = res.text().await?;
+      return Err(RestError::RequestFailed{ status, body });
+    }
+    if res.content_length().is_some_and(|length| length > limit) {
+      return Err(RestError::DownloadTooLarge { limit })
+    }
+
+    // The length header is only a hint, so the body is still counted while reading it
+    let mut data = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+      if (data.len() + chunk.len()) as u64 > limit {
+        return Err(RestError::DownloadTooLarge { limit })
+      }
+      data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+  }
+
+  /// Enables caching get responses with their `ETag`.\
+  /// Later get requests to the same url send `If-None-Match` and a `304 Not Modified` response returns the cached body instead.\
+  /// If the cached body is gone by then, the request is retried once without `If-None-Match`.\
+  /// Up to `capacity` responses are kept, evicting the oldest first. Clones of the handler share the cache.
+  ///
+  /// Not every Discord endpoint returns an `ETag` or supports conditional requests, responses without one are simply not cached.
+  /// ```no_run
+  /// # use slashook::rest::Rest;
+  /// # use slashook::structs::guilds::Guild;
+  /// # #[slashook::main]
+  /// # async fn main() -> Result<(), slashook::rest::RestError> {
+  /// let rest = Rest::with_bot_token("your.bot.token")?.with_etag_cache(100);
+  /// let guild: Guild = rest.get(String::from("guilds/613425648685547541")).await?;
+  /// // Served from the cache if the guild hasn't changed
+  /// let guild: Guild = rest.get(String::from("guilds/613425648685547541")).await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn with_etag_cache(mut self, capacity: usize) -> Self {
+    self.etag_cache = Some(Arc::new(Mutex::new(EtagCache::new(capacity))));
+    self
+  }
+
+  async fn send_get<T: DeserializeOwned + 'static>(&self, req: RequestBuilder) -> Result<T, RestError> {
+    let Some(cache) = &self.etag_cache else {
+      return handle_response(req.send().await?).await
+    };
+
+    let mut req = req.build()?;
+    let url = req.url().to_string();
+    // Keep an unconditional copy around in case the cached body disappears before the 304 arrives
+    let mut unconditional = None;
+    let cached_etag = cache.lock().unwrap().get(&url).map(|c| c.etag.clone());
+    if let Some(etag) = cached_etag.and_then(|e| HeaderValue::from_str(&e).ok()) {
+      unconditional = req.try_clone();
+      req.headers_mut().insert(IF_NONE_MATCH, etag);
+    }
+
+    let mut res = self.client.execute(req).await?;
+    if res.status() == StatusCode::NOT_MODIFIED {
+      let cached_body = cache.lock().unwrap().get(&url).map(|c| c.body.clone());
+      if let Some(body) = cached_body {
+        return deserialize_body(body)
+      }
+      if let Some(retry) = unconditional {
+        res = self.client.execute(retry).await?;
+      }
+    }
+
+    let etag = res.headers().get(ETAG).and_then(|e| e.to_str().ok()).map(String::from);
+    match etag {
+      Some(etag) if res.status().is_success() => {
+        let body = res.text().await?;
+        cache.lock().unwrap().insert(url, CachedResponse { etag, body: body.clone() });
+        deserialize_body(body)
+      },
+      _ => {
+        cache.lock().unwrap().remove(&url);
+        handle_response(res).await
+      }
+    }
   }
 
   /// Make a get request
   pub async fn get<T: DeserializeOwned + 'static>(&self, path: String) -> Result<T, RestError> {
+    let req = self.client.get(format!("{}/{}", API_URL, path));
+    self.send_get(req).await
+  }
+
+  /// Make a get request and return the status, headers and body as is.\
+  /// Unlike the typed methods, failed statuses are not turned into errors so headers such as `X-RateLimit-*` can be inspected on any response.
+  /// ```no_run
+  /// # use slashook::rest::Rest;
+  /// # #[slashook::main]
+  /// # async fn main() -> Result<(), slashook::rest::RestError> {
+  /// let rest = Rest::with_bot_token("your.bot.token")?;
+  /// let (status, headers, body) = rest.get_raw(String::from("users/@me")).await?;
+  /// let remaining = headers.get("X-RateLimit-Remaining").and_then(|v| v.to_str().ok());
+  /// println!("{} ({:?} requests remaining): {} bytes", status, remaining, body.len());
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn get_raw(&self, path: String) -> Result<(StatusCode, HeaderMap, Bytes), RestError> {
     let req = self.client.get(format!("{}/{}", API_URL, path));
     let res = req.send().await?;
-    handle_response(res).await
+    let status = res.status();
+    let headers = res.headers().clone();
+    Ok((status, headers, res.bytes().await?))
   }
 
   /// Make a get request with query parameters
   pub async fn get_query<T: DeserializeOwned + 'static, U: Serialize>(&self, path: String, query: U) -> Result<T, RestError> {
     let req = self.client.get(format!("{}/{}", API_URL, path))
       .query(&query);
-    let res = req.send().await?;
-    handle_response(res).await
+    self.send_get(req).await
   }
 
   /// Make a post request
@@ -161,6 +413,30 @@ impl Rest {
     handle_response(res).await
   }
 
+  /// Make a post request with query parameters\
+  /// Useful for create routes that take extra options such as `wait` when executing a webhook
+  /// ```no_run
+  /// # use slashook::rest::Rest;
+  /// # use slashook::structs::messages::Message;
+  /// # use slashook::commands::MessageResponse;
+  /// # #[slashook::main]
+  /// # async fn main() {
+  /// let rest = Rest::new();
+  /// let message: Message = rest.post_query(
+  ///   String::from("webhooks/1234/token"),
+  ///   [("wait", true)],
+  ///   MessageResponse::from("Hello!")
+  /// ).await.unwrap();
+  /// # }
+  /// ```
+  pub async fn post_query<T: DeserializeOwned + 'static, U: Serialize, V: Serialize>(&self, path: String, query: U, data: V) -> Result<T, RestError> {
+    let req = self.client.post(format!("{}/{}", API_URL, path))
+      .query(&query)
+      .json(&data);
+    let res = req.send().await?;
+    handle_response(res).await
+  }
+
   /// Make a post request including files
   pub async fn post_files<T: DeserializeOwned + 'static, U: Serialize + Attachments>(&self, path: String, json_data: U, files: Vec<File>) -> Result<T, RestError> {
     let form_data = handle_multipart(json_data, files)?;
@@ -170,6 +446,16 @@ impl Rest {
     handle_response(res).await
   }
 
+  /// Make a post request including files with query parameters
+  pub async fn post_files_query<T: DeserializeOwned + 'static, U: Serialize, V: Serialize + Attachments>(&self, path: String, query: U, json_data: V, files: Vec<File>) -> Result<T, RestError> {
+    let form_data = handle_multipart(json_data, files)?;
+    let req = self.client.post(format!("{}/{}", API_URL, path))
+      .query(&query)
+      .multipart(form_data);
+    let res = req.send().await?;
+    handle_response(res).await
+  }
+
   /// Make a patch request
   pub async fn patch<T: DeserializeOwned + 'static, U: Serialize>(&self, path: String, data: U) -> Result<T, RestError> {
     let req = self.client.patch(format!("{}/{}", API_URL, path))
@@ -178,6 +464,17 @@ impl Rest {
     handle_response(res).await
   }
 
+  /// Make a patch request with an optional reason shown in the guild's audit log
+  pub async fn patch_with_reason<T: DeserializeOwned + 'static, U: Serialize>(&self, path: String, data: U, reason: Option<&str>) -> Result<T, RestError> {
+    let mut req = self.client.patch(format!("{}/{}", API_URL, path))
+      .json(&data);
+    if let Some(reason) = reason {
+      req = req.header(AUDIT_LOG_REASON, encode_reason(reason));
+    }
+    let res = req.send().await?;
+    handle_response(res).await
+  }
+
   /// Make a patch request including files
   pub async fn patch_files<T: DeserializeOwned + 'static, U: Serialize + Attachments>(&self, path: String, json_data: U, files: Vec<File>) -> Result<T, RestError> {
     let form_data = handle_multipart(json_data, files)?;
@@ -16,6 +16,7 @@ use super::{
   guilds::Guild,
   interactions::IntegrationType,
 };
+use crate::rest::{Rest, RestError};
 use bitflags::bitflags;
 
 /// Discord Application Object
@@ -79,6 +80,55 @@ pub struct Application {
   pub custom_install_url: Option<String>,
 }
 
+impl Application {
+  /// Fetch the application of the bot token used
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::applications::Application;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let application = Application::fetch_current(&input.rest).await?;
+  /// res.send_message(format!("Invite me: {}", application.install_url())).await?;
+  /// # }
+  /// ```
+  pub async fn fetch_current(rest: &Rest) -> Result<Self, RestError> {
+    rest.get(String::from("applications/@me")).await
+  }
+
+  /// Returns the link for installing the application.\
+  /// This is the custom install url if one is set. Otherwise the OAuth2 authorization url is built from the install params
+  /// or the guild install params, and if neither are set, the link lets Discord use the defaults set for the application.
+  /// ```
+  /// # use slashook::structs::applications::Application;
+  /// let application: Application = serde_json::from_value(serde_json::json!({
+  ///   "id": "344584201290907659",
+  ///   "name": "Example",
+  ///   "icon": null,
+  ///   "description": "",
+  ///   "install_params": { "scopes": ["bot", "applications.commands"], "permissions": "2048" }
+  /// })).unwrap();
+  /// assert_eq!(
+  ///   application.install_url(),
+  ///   "https://discord.com/oauth2/authorize?client_id=344584201290907659&scope=bot%20applications.commands&permissions=2048"
+  /// );
+  /// ```
+  pub fn install_url(&self) -> String {
+    if let Some(custom_install_url) = &self.custom_install_url {
+      return custom_install_url.clone()
+    }
+
+    let guild_install_params = self.integration_types_config.as_ref()
+      .and_then(|config| config.guild_install.as_ref())
+      .map(|config| &config.oauth2_install_params);
+    let mut url = format!("https://discord.com/oauth2/authorize?client_id={}", self.id);
+    if let Some(params) = self.install_params.as_ref().or(guild_install_params) {
+      url.push_str(&format!("&scope={}&permissions={}", params.scopes.join("%20"), params.permissions.bits()));
+    }
+    url
+  }
+}
+
 bitflags! {
   /// Bitflags for Discord Application Flags
   #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
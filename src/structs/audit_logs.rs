@@ -0,0 +1,327 @@
+// Copyright 2024 slashook Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Structs related to Discord audit logs
+
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use serde_json::Value;
+use super::{
+  Snowflake,
+  channels::Channel,
+  guilds::GuildScheduledEvent,
+  interactions::ApplicationCommand,
+  users::User,
+};
+
+/// Discord Audit Log Object
+/// ```
+/// # use slashook::structs::audit_logs::{AuditLog, AuditLogEvent};
+/// let audit_log: AuditLog = serde_json::from_str(r#"{
+///   "audit_log_entries": [{
+///     "id": "1", "user_id": "2", "target_id": "3", "action_type": 22, "reason": "Spam",
+///     "changes": [{ "key": "nick", "old_value": "a", "new_value": "b" }]
+///   }, {
+///     "id": "4", "user_id": "2", "target_id": null, "action_type": 255
+///   }],
+///   "users": [], "webhooks": [], "integrations": [], "threads": []
+/// }"#).unwrap();
+/// assert_eq!(audit_log.audit_log_entries[0].action_type, AuditLogEvent::MEMBER_BAN_ADD);
+/// assert_eq!(audit_log.audit_log_entries[1].action_type, AuditLogEvent::UNKNOWN);
+/// ```
+#[derive(Deserialize, Clone, Debug)]
+pub struct AuditLog {
+  /// List of application commands referenced in the audit log
+  #[serde(default)]
+  pub application_commands: Vec<ApplicationCommand>,
+  /// List of audit log entries, sorted from most to least recent
+  #[serde(default)]
+  pub audit_log_entries: Vec<AuditLogEntry>,
+  /// List of auto moderation rules referenced in the audit log
+  #[serde(default)]
+  pub auto_moderation_rules: Vec<Value>,
+  /// List of guild scheduled events referenced in the audit log
+  #[serde(default)]
+  pub guild_scheduled_events: Vec<GuildScheduledEvent>,
+  /// List of partial integration objects
+  #[serde(default)]
+  pub integrations: Vec<Value>,
+  /// List of threads referenced in the audit log
+  #[serde(default)]
+  pub threads: Vec<Channel>,
+  /// List of users referenced in the audit log
+  #[serde(default)]
+  pub users: Vec<User>,
+  /// List of webhooks referenced in the audit log
+  #[serde(default)]
+  pub webhooks: Vec<Value>,
+}
+
+/// Discord Audit Log Entry Object
+#[derive(Deserialize, Clone, Debug)]
+pub struct AuditLogEntry {
+  /// ID of the affected entity (webhook, user, role, etc.)
+  pub target_id: Option<Snowflake>,
+  /// Changes made to the target_id
+  pub changes: Option<Vec<AuditLogChange>>,
+  /// User or app that made the changes
+  pub user_id: Option<Snowflake>,
+  /// ID of the entry
+  pub id: Snowflake,
+  /// [Type of action](AuditLogEvent) that occurred
+  pub action_type: AuditLogEvent,
+  /// Additional info for certain event types
+  pub options: Option<OptionalAuditEntryInfo>,
+  /// Reason for the change (1-512 characters)
+  pub reason: Option<String>,
+}
+
+/// Discord Audit Log Change Object
+#[derive(Deserialize, Clone, Debug)]
+pub struct AuditLogChange {
+  /// New value of the key
+  pub new_value: Option<Value>,
+  /// Old value of the key
+  pub old_value: Option<Value>,
+  /// Name of the changed entity, with a few exceptions
+  pub key: String,
+}
+
+/// Discord Optional Audit Entry Info Object
+#[derive(Deserialize, Clone, Debug)]
+pub struct OptionalAuditEntryInfo {
+  /// ID of the app whose permissions were targeted
+  pub application_id: Option<Snowflake>,
+  /// Name of the Auto Moderation rule that was triggered
+  pub auto_moderation_rule_name: Option<String>,
+  /// Trigger type of the Auto Moderation rule that was triggered
+  pub auto_moderation_rule_trigger_type: Option<String>,
+  /// Channel in which the entities were targeted
+  pub channel_id: Option<Snowflake>,
+  /// Number of entities that were targeted
+  pub count: Option<String>,
+  /// Number of days after which inactive members were kicked
+  pub delete_member_days: Option<String>,
+  /// ID of the overwritten entity
+  pub id: Option<Snowflake>,
+  /// Number of members removed by the prune
+  pub members_removed: Option<String>,
+  /// ID of the message that was targeted
+  pub message_id: Option<Snowflake>,
+  /// Name of the role if type is `"0"` (not present if type is `"1"`)
+  pub role_name: Option<String>,
+  /// Type of overwritten entity - role (`"0"`) or member (`"1"`)
+  #[serde(rename = "type")]
+  pub overwrite_type: Option<String>,
+  /// The type of integration which performed the action
+  pub integration_type: Option<String>,
+}
+
+/// Discord Audit Log Events
+#[derive(Deserialize_repr, Serialize_repr, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[allow(non_camel_case_types)]
+pub enum AuditLogEvent {
+  /// Server settings were updated
+  GUILD_UPDATE = 1,
+  /// Channel was created
+  CHANNEL_CREATE = 10,
+  /// Channel settings were updated
+  CHANNEL_UPDATE = 11,
+  /// Channel was deleted
+  CHANNEL_DELETE = 12,
+  /// Permission overwrite was added to a channel
+  CHANNEL_OVERWRITE_CREATE = 13,
+  /// Permission overwrite was updated for a channel
+  CHANNEL_OVERWRITE_UPDATE = 14,
+  /// Permission overwrite was deleted from a channel
+  CHANNEL_OVERWRITE_DELETE = 15,
+  /// Member was removed from server
+  MEMBER_KICK = 20,
+  /// Members were pruned from server
+  MEMBER_PRUNE = 21,
+  /// Member was banned from server
+  MEMBER_BAN_ADD = 22,
+  /// Server ban was lifted for a member
+  MEMBER_BAN_REMOVE = 23,
+  /// Member was updated in server
+  MEMBER_UPDATE = 24,
+  /// Member was added or removed from a role
+  MEMBER_ROLE_UPDATE = 25,
+  /// Member was moved to a different voice channel
+  MEMBER_MOVE = 26,
+  /// Member was disconnected from a voice channel
+  MEMBER_DISCONNECT = 27,
+  /// Bot user was added to server
+  BOT_ADD = 28,
+  /// Role was created
+  ROLE_CREATE = 30,
+  /// Role was edited
+  ROLE_UPDATE = 31,
+  /// Role was deleted
+  ROLE_DELETE = 32,
+  /// Server invite was created
+  INVITE_CREATE = 40,
+  /// Server invite was updated
+  INVITE_UPDATE = 41,
+  /// Server invite was deleted
+  INVITE_DELETE = 42,
+  /// Webhook was created
+  WEBHOOK_CREATE = 50,
+  /// Webhook properties or channel were updated
+  WEBHOOK_UPDATE = 51,
+  /// Webhook was deleted
+  WEBHOOK_DELETE = 52,
+  /// Emoji was created
+  EMOJI_CREATE = 60,
+  /// Emoji name was updated
+  EMOJI_UPDATE = 61,
+  /// Emoji was deleted
+  EMOJI_DELETE = 62,
+  /// Single message was deleted
+  MESSAGE_DELETE = 72,
+  /// Multiple messages were deleted
+  MESSAGE_BULK_DELETE = 73,
+  /// Message was pinned to a channel
+  MESSAGE_PIN = 74,
+  /// Message was unpinned from a channel
+  MESSAGE_UNPIN = 75,
+  /// App was added to server
+  INTEGRATION_CREATE = 80,
+  /// App was updated (as an example, its scopes were updated)
+  INTEGRATION_UPDATE = 81,
+  /// App was removed from server
+  INTEGRATION_DELETE = 82,
+  /// Stage instance was created (stage channel becomes live)
+  STAGE_INSTANCE_CREATE = 83,
+  /// Stage instance details were updated
+  STAGE_INSTANCE_UPDATE = 84,
+  /// Stage instance was deleted (stage channel no longer live)
+  STAGE_INSTANCE_DELETE = 85,
+  /// Sticker was created
+  STICKER_CREATE = 90,
+  /// Sticker details were updated
+  STICKER_UPDATE = 91,
+  /// Sticker was deleted
+  STICKER_DELETE = 92,
+  /// Event was created
+  GUILD_SCHEDULED_EVENT_CREATE = 100,
+  /// Event was updated
+  GUILD_SCHEDULED_EVENT_UPDATE = 101,
+  /// Event was cancelled
+  GUILD_SCHEDULED_EVENT_DELETE = 102,
+  /// Thread was created in a channel
+  THREAD_CREATE = 110,
+  /// Thread was updated
+  THREAD_UPDATE = 111,
+  /// Thread was deleted
+  THREAD_DELETE = 112,
+  /// Permissions were updated for a command
+  APPLICATION_COMMAND_PERMISSION_UPDATE = 121,
+  /// Soundboard sound was created
+  SOUNDBOARD_SOUND_CREATE = 130,
+  /// Soundboard sound was updated
+  SOUNDBOARD_SOUND_UPDATE = 131,
+  /// Soundboard sound was deleted
+  SOUNDBOARD_SOUND_DELETE = 132,
+  /// Auto Moderation rule was created
+  AUTO_MODERATION_RULE_CREATE = 140,
+  /// Auto Moderation rule was updated
+  AUTO_MODERATION_RULE_UPDATE = 141,
+  /// Auto Moderation rule was deleted
+  AUTO_MODERATION_RULE_DELETE = 142,
+  /// Message was blocked by Auto Moderation
+  AUTO_MODERATION_BLOCK_MESSAGE = 143,
+  /// Message was flagged by Auto Moderation
+  AUTO_MODERATION_FLAG_TO_CHANNEL = 144,
+  /// Member was timed out by Auto Moderation
+  AUTO_MODERATION_USER_COMMUNICATION_DISABLED = 145,
+  /// Creator monetization request was created
+  CREATOR_MONETIZATION_REQUEST_CREATED = 150,
+  /// Creator monetization terms were accepted
+  CREATOR_MONETIZATION_TERMS_ACCEPTED = 151,
+  /// Guild Onboarding Question was created
+  ONBOARDING_PROMPT_CREATE = 163,
+  /// Guild Onboarding Question was updated
+  ONBOARDING_PROMPT_UPDATE = 164,
+  /// Guild Onboarding Question was deleted
+  ONBOARDING_PROMPT_DELETE = 165,
+  /// Guild Onboarding was created
+  ONBOARDING_CREATE = 166,
+  /// Guild Onboarding was updated
+  ONBOARDING_UPDATE = 167,
+  /// Guild Server Guide was created
+  HOME_SETTINGS_CREATE = 190,
+  /// Guild Server Guide was updated
+  HOME_SETTINGS_UPDATE = 191,
+  /// Audit log event that hasn't been implemented yet
+  #[serde(other)]
+  UNKNOWN
+}
+
+/// Parameters for fetching an audit log with [fetch_audit_log](super::guilds::Guild::fetch_audit_log)
+#[derive(Serialize, Default, Clone, Debug)]
+pub struct AuditLogFetchOptions {
+  /// Entries from a specific user ID
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub user_id: Option<Snowflake>,
+  /// Entries for a specific [audit log event](AuditLogEvent)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub action_type: Option<AuditLogEvent>,
+  /// Entries with ID less than a specific audit log entry ID
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub before: Option<Snowflake>,
+  /// Entries with ID greater than a specific audit log entry ID
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub after: Option<Snowflake>,
+  /// Maximum number of entries (between 1-100) to return, defaults to 50
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub limit: Option<i64>,
+}
+
+impl AuditLogFetchOptions {
+  /// Creates a new empty AuditLogFetchOptions
+  pub fn new() -> Self {
+    Self {
+      user_id: None,
+      action_type: None,
+      before: None,
+      after: None,
+      limit: None,
+    }
+  }
+
+  /// Sets the user id
+  pub fn set_user_id<T: ToString>(mut self, user_id: T) -> Self {
+    self.user_id = Some(user_id.to_string());
+    self
+  }
+
+  /// Sets the action type
+  pub fn set_action_type(mut self, action_type: AuditLogEvent) -> Self {
+    self.action_type = Some(action_type);
+    self
+  }
+
+  /// Sets before
+  pub fn set_before<T: ToString>(mut self, before: T) -> Self {
+    self.before = Some(before.to_string());
+    self
+  }
+
+  /// Sets after
+  pub fn set_after<T: ToString>(mut self, after: T) -> Self {
+    self.after = Some(after.to_string());
+    self
+  }
+
+  /// Sets the limit
+  pub fn set_limit(mut self, limit: i64) -> Self {
+    self.limit = Some(limit);
+    self
+  }
+}
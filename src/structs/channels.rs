@@ -13,17 +13,19 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use serde_json::{Value, json};
 use super::{
   Snowflake,
-  guilds::GuildMember,
+  guilds::{GuildMember, Role},
   interactions::Attachments,
   invites::{Invite, CreateInviteOptions},
   messages::{Message, MessageFetchOptions, Attachment},
   permissions::Permissions,
   users::User,
+  utils::ValidationError,
 };
 use crate::{
   rest::{Rest, RestError},
   commands::MessageResponse
 };
+use crate::tokio::{spawn, task::JoinHandle, time::{sleep, Duration}};
 use chrono::{DateTime, Utc};
 use bitflags::bitflags;
 
@@ -89,11 +91,11 @@ pub struct Channel {
   pub flags: Option<ChannelFlags>,
   /// Number of messages ever sent in a thread, it's similar to `message_count` on message creation, but will not decrement the number when a message is deleted
   pub total_message_sent: Option<i64>,
-  /// The set of tags that can be used in a `GUILD_FORUM` channel
+  /// The set of tags that can be used in a `GUILD_FORUM` or `GUILD_MEDIA` channel
   pub available_tags: Option<Vec<ForumTag>>,
-  /// The IDs of the set of tags that have been applied to a thread in a `GUILD_FORUM` channel
+  /// The IDs of the set of tags that have been applied to a thread in a `GUILD_FORUM` or `GUILD_MEDIA` channel
   pub applied_tags: Option<Vec<Snowflake>>,
-  /// The emoji to show in the add reaction button on a thread in a `GUILD_FORUM` channel
+  /// The emoji to show in the add reaction button on a thread in a `GUILD_FORUM` or `GUILD_MEDIA` channel
   pub default_reaction_emoji: Option<DefaultReaction>,
   /// The initial `rate_limit_per_user` to set on newly created threads in a channel. This field is copied to the thread at creation time and does not live update.
   pub default_thread_rate_limit_per_user: Option<i64>,
@@ -105,8 +107,22 @@ pub struct Channel {
   pub message: Option<Box<Message>>,
 }
 
+/// Guard that keeps a typing indicator active. Created with [typing_guard](Channel::typing_guard).
+///
+/// The typing indicator is no longer refreshed once this is dropped.
+#[derive(Debug)]
+pub struct TypingGuard {
+  handle: JoinHandle<()>
+}
+
+impl Drop for TypingGuard {
+  fn drop(&mut self) {
+    self.handle.abort();
+  }
+}
+
 /// Discord Channel Types
-#[derive(Deserialize_repr, Serialize_repr, Clone, Debug)]
+#[derive(Deserialize_repr, Serialize_repr, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
 pub enum ChannelType {
@@ -124,7 +140,7 @@ pub enum ChannelType {
   GUILD_ANNOUNCEMENT = 5,
   /// A temporary sub-channel within a GUILD_ANNOUNCEMENT channel
   ANNOUNCEMENT_THREAD = 10,
-  /// A temporary sub-channel within a GUILD_TEXT or GUILD_FORUM channel
+  /// A temporary sub-channel within a GUILD_TEXT, GUILD_FORUM or GUILD_MEDIA channel
   GUILD_PUBLIC_THREAD = 11,
   /// A temporary sub-channel within a GUILD_TEXT channel that is only viewable by those invited and those with the MANAGE_THREADS permission
   GUILD_PRIVATE_THREAD = 12,
@@ -134,6 +150,8 @@ pub enum ChannelType {
   GUILD_DIRECTORY = 14,
   /// Channel that can only contain threads
   GUILD_FORUM = 15,
+  /// Channel that can only contain threads, similar to `GUILD_FORUM` channels
+  GUILD_MEDIA = 16,
   /// Channel type that hasn't been implemented yet
   #[serde(other)]
   UNKNOWN
@@ -217,10 +235,12 @@ bitflags! {
   /// Bitflags for Discord Channel Flags
   #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
   pub struct ChannelFlags: u32 {
-    /// This thread is pinned to the top of its parent `GUILD_FORUM` channel
+    /// This thread is pinned to the top of its parent `GUILD_FORUM` or `GUILD_MEDIA` channel
     const PINNED = 1 << 1;
-    /// Whether a tag is required to be specified when creating a thread in a `GUILD_FORUM` channel. Tags are specified in the `applied_tags` field.
+    /// Whether a tag is required to be specified when creating a thread in a `GUILD_FORUM` or `GUILD_MEDIA` channel. Tags are specified in the `applied_tags` field.
     const REQUIRE_TAG = 1 << 4;
+    /// When set hides the embedded media download options. Available only for media channels
+    const HIDE_MEDIA_DOWNLOAD_OPTIONS = 1 << 15;
   }
 }
 
@@ -335,10 +355,10 @@ pub struct ChannelModifyOptions {
   /// [Channel flags](ChannelFlags) combined as a [bitfield](https://en.wikipedia.org/wiki/Bit_field). Currently only `REQUIRE_TAG` is supported in forum channels and `PINNED` can only be set for threads in forum channels.
   #[serde(skip_serializing_if = "Option::is_none")]
   pub flags: Option<ChannelFlags>,
-  /// The set of tags that can be used in a `GUILD_FORUM` channel
+  /// The set of tags that can be used in a `GUILD_FORUM` or `GUILD_MEDIA` channel
   #[serde(skip_serializing_if = "Option::is_none")]
   pub available_tags: Option<Vec<ForumTag>>,
-  /// The emoji to show in the add reaction button on a thread in a `GUILD_FORUM` channel
+  /// The emoji to show in the add reaction button on a thread in a `GUILD_FORUM` or `GUILD_MEDIA` channel
   #[serde(skip_serializing_if = "Option::is_none")]
   pub default_reaction_emoji: Option<DefaultReaction>,
   /// The initial `rate_limit_per_user` to set on newly created threads in a channel. This field is copied to the thread at creation time and does not live update.
@@ -362,7 +382,7 @@ pub struct ChannelModifyOptions {
   /// Whether non-moderators can add other non-moderators to a thread; only available on private threads
   #[serde(skip_serializing_if = "Option::is_none")]
   pub invitable: Option<bool>,
-  /// The IDs of the set of tags that have been applied to a thread in a `GUILD_FORUM` channel
+  /// The IDs of the set of tags that have been applied to a thread in a `GUILD_FORUM` or `GUILD_MEDIA` channel
   #[serde(skip_serializing_if = "Option::is_none")]
   pub applied_tags: Option<Vec<Snowflake>>,
 }
@@ -385,6 +405,9 @@ pub struct ThreadCreateOptions {
   pub message: Option<MessageResponse>,
   /// The IDs of the set of tags that have been applied to a thread in a `GUILD_FORUM` or a `GUILD_MEDIA` channel
   pub applied_tags: Option<Vec<Snowflake>>,
+  /// [Channel flags](ChannelFlags) combined as a [bitfield](https://en.wikipedia.org/wiki/Bit_field). Currently only `PINNED` can be set, for threads in forum channels.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub flags: Option<ChannelFlags>,
 }
 
 /// Options for fetching thread members
@@ -482,6 +505,33 @@ impl Channel {
     Message::create(rest, &self.id, message).await
   }
 
+  /// Sends a new message to this channel in the background without waiting for it to be sent.\
+  /// The returned handle can be awaited for the result, or dropped if the message isn't needed.
+  /// Errors are logged either way, so failures aren't lost when the handle is dropped.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::channels::Channel;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let log_channel = Channel::fetch(&input.rest, "613430047285706767").await?;
+  /// log_channel.send(&input.rest, format!("{} used /example", input.user.username));
+  /// res.send_message("Done!").await?;
+  /// # }
+  /// ```
+  pub fn send<T: Into<MessageResponse>>(&self, rest: &Rest, message: T) -> JoinHandle<Result<Message, RestError>> {
+    let rest = rest.clone();
+    let channel_id = self.id.clone();
+    let message = message.into();
+    spawn(async move {
+      let result = Message::create(&rest, &channel_id, message).await;
+      if let Err(err) = &result {
+        eprintln!("Failed to send a message to channel {}: {:?}", channel_id, err);
+      }
+      result
+    })
+  }
+
   /// Delete multiple messages from this channel.\
   /// 2-100 message IDs can be provided at once.
   /// ```
@@ -559,7 +609,66 @@ impl Channel {
     rest.post(format!("channels/{}/invites", self.id), options).await
   }
 
-  /// Follows an announcement channel to send messages to the target channel
+  /// Returns true if the channel is an [Announcement channel](ChannelType::GUILD_ANNOUNCEMENT) whose messages can be followed and crossposted
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::{channels::Channel, messages::Message};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let channel = Channel::fetch(&input.rest, "344581598878105605").await?;
+  /// let msg = channel.create_message(&input.rest, "Hello!").await?;
+  /// if channel.is_announcement() {
+  ///   msg.crosspost(&input.rest).await?;
+  /// }
+  /// # }
+  /// ```
+  pub fn is_announcement(&self) -> bool {
+    matches!(self.channel_type, ChannelType::GUILD_ANNOUNCEMENT)
+  }
+
+  /// Returns true if the channel is a thread. See [`ChannelType::is_thread`]
+  pub fn is_thread(&self) -> bool {
+    self.channel_type.is_thread()
+  }
+
+  /// Returns true if the channel is a forum or media channel. See [`ChannelType::is_thread_only`]
+  pub fn is_thread_only(&self) -> bool {
+    self.channel_type.is_thread_only()
+  }
+
+  /// Returns true if the channel is a voice channel. See [`ChannelType::is_voice`]
+  pub fn is_voice(&self) -> bool {
+    self.channel_type.is_voice()
+  }
+
+  /// Returns true if messages can be sent in the channel. See [`ChannelType::is_text_based`]
+  pub fn is_text_based(&self) -> bool {
+    self.channel_type.is_text_based()
+  }
+
+  /// Returns true if the channel belongs to a guild. See [`ChannelType::is_guild`]
+  pub fn is_guild(&self) -> bool {
+    self.channel_type.is_guild()
+  }
+
+  /// Returns true if the channel is a direct message channel. See [`ChannelType::is_dm`]
+  pub fn is_dm(&self) -> bool {
+    self.channel_type.is_dm()
+  }
+
+  /// Returns the tags that can be applied to threads in a forum or media channel, or an empty slice for other channels
+  pub fn available_tags(&self) -> &[ForumTag] {
+    self.available_tags.as_deref().unwrap_or_default()
+  }
+
+  /// Finds an available tag by its name, ignoring case
+  pub fn find_tag(&self, name: &str) -> Option<&ForumTag> {
+    self.available_tags().iter().find(|tag| tag.name.to_lowercase() == name.to_lowercase())
+  }
+
+  /// Follows an announcement channel to send messages to the target channel.\
+  /// Only [Announcement channels](Channel::is_announcement) can be followed.
   pub async fn follow<T: ToString>(&self, rest: &Rest, target_channel_id: T) -> Result<FollowedChannel, RestError> {
     let body = json!({ "webhook_channel_id": target_channel_id.to_string() });
     rest.post(format!("channels/{}/followers", self.id), body).await
@@ -570,6 +679,32 @@ impl Channel {
     rest.post(format!("channels/{}/typing", self.id), Value::Null).await
   }
 
+  /// Keep a typing indicator active in the channel until the returned guard is dropped.\
+  /// The indicator is triggered again every 8 seconds and stops if triggering it fails.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::channels::Channel;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let channel = Channel::fetch(&input.rest, "613430047285706767").await?;
+  /// let typing = channel.typing_guard(&input.rest);
+  /// // Do something that takes a while
+  /// drop(typing);
+  /// channel.create_message(&input.rest, "Done!").await?;
+  /// # }
+  /// ```
+  pub fn typing_guard(&self, rest: &Rest) -> TypingGuard {
+    let channel = self.clone();
+    let rest = rest.clone();
+    let handle = spawn(async move {
+      while channel.trigger_typing(&rest).await.is_ok() {
+        sleep(Duration::from_secs(8)).await;
+      }
+    });
+    TypingGuard { handle }
+  }
+
   /// Get all pinned messages in the channel
   /// ```
   /// # #[macro_use] extern crate slashook;
@@ -729,6 +864,72 @@ impl Channel {
   pub async fn list_joined_private_archived_threads(&self, rest: &Rest, options: ThreadListOptions) -> Result<ThreadListResponse, RestError> {
     rest.get_query(format!("channels/{}/users/@me/threads/archived/private", self.id), options).await
   }
+
+  /// Computes the permissions a member has in the channel from their roles and the channel's permission overwrites.\
+  /// `roles` should contain the member's roles, other roles are ignored. The guild owner having all permissions is not taken into account.
+  /// ```
+  /// # use slashook::structs::{channels::Channel, guilds::{GuildMember, Role}, Permissions};
+  /// # fn main() -> Result<(), serde_json::Error> {
+  /// let everyone: Role = serde_json::from_value(serde_json::json!({
+  ///   "id": "1", "name": "@everyone", "color": 0, "hoist": false, "position": 0,
+  ///   "permissions": "3072", "managed": false, "mentionable": false
+  /// }))?;
+  /// let member: GuildMember = serde_json::from_value(serde_json::json!({
+  ///   "user": { "id": "2", "username": "user", "discriminator": "0", "avatar": null },
+  ///   "roles": [], "joined_at": "2024-01-01T00:00:00Z", "flags": 0
+  /// }))?;
+  /// let channel: Channel = serde_json::from_value(serde_json::json!({
+  ///   "id": "3", "type": 0, "guild_id": "1",
+  ///   "permission_overwrites": [
+  ///     { "id": "1", "type": 0, "allow": "0", "deny": "2048" },
+  ///     { "id": "2", "type": 1, "allow": "2048", "deny": "0" }
+  ///   ]
+  /// }))?;
+  /// let permissions = channel.compute_permissions(&member, &[], &everyone);
+  /// assert_eq!(permissions, Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES);
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn compute_permissions(&self, member: &GuildMember, roles: &[Role], everyone_role: &Role) -> Permissions {
+    let member_roles = roles.iter().filter(|r| member.roles.contains(&r.id));
+
+    let mut permissions = everyone_role.permissions;
+    for role in member_roles {
+      permissions |= role.permissions;
+    }
+
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+      return Permissions::all();
+    }
+
+    let overwrites = match &self.permission_overwrites {
+      Some(overwrites) => overwrites,
+      None => return permissions
+    };
+
+    if let Some(overwrite) = overwrites.iter().find(|o| o.id == everyone_role.id) {
+      permissions &= !overwrite.deny;
+      permissions |= overwrite.allow;
+    }
+
+    let mut allow = Permissions::empty();
+    let mut deny = Permissions::empty();
+    for overwrite in overwrites.iter().filter(|o| matches!(o.overwrite_type, PermissionOverwriteType::ROLE) && member.roles.contains(&o.id)) {
+      allow |= overwrite.allow;
+      deny |= overwrite.deny;
+    }
+    permissions &= !deny;
+    permissions |= allow;
+
+    if let Some(user) = &member.user {
+      if let Some(overwrite) = overwrites.iter().find(|o| matches!(o.overwrite_type, PermissionOverwriteType::MEMBER) && o.id == user.id) {
+        permissions &= !overwrite.deny;
+        permissions |= overwrite.allow;
+      }
+    }
+
+    permissions
+  }
 }
 
 impl ChannelModifyOptions {
@@ -927,6 +1128,7 @@ impl ThreadCreateOptions {
       rate_limit_per_user: None,
       message: None,
       applied_tags: None,
+      flags: None,
     }
   }
 
@@ -954,7 +1156,31 @@ impl ThreadCreateOptions {
     self
   }
 
-  /// Sets the message
+  /// Sets the message. Components on the message are kept whether or not it has files.
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::channels::ThreadCreateOptions;
+  /// # use slashook::structs::components::{Components, Button};
+  /// # use slashook::structs::interactions::Attachments;
+  /// # use slashook::structs::messages::Attachment;
+  /// # use slashook::structs::utils::File;
+  /// let components = Components::new().add_button(Button::new().set_label("Close").set_id("support", "close"));
+  /// let message = MessageResponse::from("How can we help?").set_components(components);
+  ///
+  /// // Without files the options are sent as JSON
+  /// let options = ThreadCreateOptions::new("Support").set_message(message.clone());
+  /// let json = serde_json::to_value(&options).unwrap();
+  /// assert_eq!(json["message"]["components"][0]["components"][0]["custom_id"], "support/close");
+  ///
+  /// // With files the same options are sent as the payload_json of a multipart body
+  /// let mut options = ThreadCreateOptions::new("Support").set_message(message.add_file(File::new("log.txt", "...")));
+  /// let files = options.message.as_mut().unwrap().files.take().unwrap();
+  /// let attachments = Attachment::for_upload(options.take_attachments(), &files);
+  /// options.set_attachments(attachments);
+  /// let json = serde_json::to_value(&options).unwrap();
+  /// assert_eq!(json["message"]["components"][0]["components"][0]["custom_id"], "support/close");
+  /// assert_eq!(json["message"]["attachments"][0]["filename"], "log.txt");
+  /// ```
   pub fn set_message<T: Into<MessageResponse>>(mut self, message: T) -> Self {
     self.message = Some(message.into());
     self
@@ -965,6 +1191,47 @@ impl ThreadCreateOptions {
     self.applied_tags = Some(tags);
     self
   }
+
+  /// Sets flags
+  /// ```
+  /// # use slashook::structs::channels::{ThreadCreateOptions, ChannelFlags};
+  /// let options = ThreadCreateOptions::new("Announcements")
+  ///   .set_message("Read this first")
+  ///   .set_flags(ChannelFlags::PINNED);
+  /// let json = serde_json::to_value(&options).unwrap();
+  /// assert_eq!(json["flags"], 2);
+  /// ```
+  pub fn set_flags(mut self, flags: ChannelFlags) -> Self {
+    self.flags = Some(flags);
+    self
+  }
+
+  /// Applies a tag by its name, resolving the id from the `available_tags` of the forum or media channel the thread is created in.
+  /// Fails if the channel has no tag with that name.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::channels::{Channel, ThreadCreateOptions};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let forum = Channel::fetch(&input.rest, "1228314225284366376").await?;
+  /// let options = ThreadCreateOptions::new("My printer is on fire")
+  ///   .set_message("Please help")
+  ///   .apply_tag_by_name(&forum, "Hardware")?
+  ///   .apply_tag_by_name(&forum, "Urgent")?;
+  /// forum.start_thread(&input.rest, options).await?;
+  /// # }
+  /// ```
+  pub fn apply_tag_by_name(mut self, channel: &Channel, name: &str) -> Result<Self, ValidationError> {
+    let tag = channel.find_tag(name)
+      .ok_or_else(|| ValidationError(format!("Channel {} has no tag named {}", channel.id, name)))?;
+    let mut tags = self.applied_tags.unwrap_or_default();
+    if !tags.contains(&tag.id) {
+      tags.push(tag.id.clone());
+    }
+    self.applied_tags = Some(tags);
+    Ok(self)
+  }
 }
 
 impl ThreadMemberOptions {
@@ -1018,6 +1285,72 @@ impl ThreadListOptions {
   }
 }
 
+impl ChannelType {
+  /// Returns true for thread channels
+  /// ```
+  /// # use slashook::structs::channels::ChannelType;
+  /// assert!(ChannelType::GUILD_PUBLIC_THREAD.is_thread());
+  /// assert!(!ChannelType::GUILD_FORUM.is_thread());
+  /// ```
+  pub fn is_thread(&self) -> bool {
+    matches!(self, Self::ANNOUNCEMENT_THREAD | Self::GUILD_PUBLIC_THREAD | Self::GUILD_PRIVATE_THREAD)
+  }
+
+  /// Returns true for forum and media channels, which can only contain threads created with [`start_thread`](Channel::start_thread)
+  /// ```
+  /// # use slashook::structs::channels::ChannelType;
+  /// assert!(ChannelType::GUILD_MEDIA.is_thread_only());
+  /// assert!(!ChannelType::GUILD_TEXT.is_thread_only());
+  /// ```
+  pub fn is_thread_only(&self) -> bool {
+    matches!(self, Self::GUILD_FORUM | Self::GUILD_MEDIA)
+  }
+
+  /// Returns true for channels users can connect to with voice
+  /// ```
+  /// # use slashook::structs::channels::ChannelType;
+  /// assert!(ChannelType::GUILD_STAGE_VOICE.is_voice());
+  /// assert!(!ChannelType::GUILD_TEXT.is_voice());
+  /// ```
+  pub fn is_voice(&self) -> bool {
+    matches!(self, Self::GUILD_VOICE | Self::GUILD_STAGE_VOICE)
+  }
+
+  /// Returns true for channels that messages can be sent in directly, including the text chat of voice channels
+  /// ```
+  /// # use slashook::structs::channels::ChannelType;
+  /// assert!(ChannelType::GUILD_VOICE.is_text_based());
+  /// assert!(ChannelType::DM.is_text_based());
+  /// assert!(!ChannelType::GUILD_FORUM.is_text_based());
+  /// ```
+  pub fn is_text_based(&self) -> bool {
+    matches!(self,
+      Self::GUILD_TEXT | Self::DM | Self::GUILD_VOICE | Self::GROUP_DM | Self::GUILD_ANNOUNCEMENT |
+      Self::ANNOUNCEMENT_THREAD | Self::GUILD_PUBLIC_THREAD | Self::GUILD_PRIVATE_THREAD | Self::GUILD_STAGE_VOICE
+    )
+  }
+
+  /// Returns true for channels that belong to a guild
+  /// ```
+  /// # use slashook::structs::channels::ChannelType;
+  /// assert!(ChannelType::GUILD_CATEGORY.is_guild());
+  /// assert!(!ChannelType::GROUP_DM.is_guild());
+  /// ```
+  pub fn is_guild(&self) -> bool {
+    !matches!(self, Self::DM | Self::GROUP_DM | Self::UNKNOWN)
+  }
+
+  /// Returns true for direct message channels, including group DMs
+  /// ```
+  /// # use slashook::structs::channels::ChannelType;
+  /// assert!(ChannelType::GROUP_DM.is_dm());
+  /// assert!(!ChannelType::GUILD_TEXT.is_dm());
+  /// ```
+  pub fn is_dm(&self) -> bool {
+    matches!(self, Self::DM | Self::GROUP_DM)
+  }
+}
+
 impl TryFrom<u8> for ChannelType {
   type Error = serde_json::Error;
 
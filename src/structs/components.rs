@@ -7,12 +7,14 @@
 
 //! Structs related to Discord message components
 
+use std::{collections::HashSet, fmt};
 use serde::{Serialize, Deserialize};
 use serde::de;
 use serde_json::Value;
 use serde_repr::{Serialize_repr, Deserialize_repr};
 use super::{
   channels::ChannelType,
+  utils::ValidationError,
   Emoji,
   Snowflake
 };
@@ -60,14 +62,52 @@ pub enum Component {
 }
 
 /// A helper struct for building components for a message
-#[derive(Clone, Debug)]
+///
+/// Components (de)serialize as the list of top-level components, so a layout can be stored as JSON and built again later.
+/// ```
+/// # use slashook::structs::components::{Components, Button};
+/// let components = Components::new().add_button(Button::new().set_label("Hi").set_id("greet", "hi"));
+/// let json = serde_json::to_string(&components).unwrap();
+/// let restored: Components = serde_json::from_str(&json).unwrap();
+/// assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(transparent)]
 pub struct Components(pub Vec<Component>);
 
+/// A helper for building and parsing `custom_id`s.
+///
+/// The library routes component and modal interactions by splitting the `custom_id` on the first `/`.
+/// The part before it is the name of the command to run and everything after it is passed to the command as [`custom_id`](crate::commands::CommandInput::custom_id).
+/// Further `/`-separated segments can be used to carry extra state such as a page number, so segments themselves shouldn't contain `/`.
+/// Like any `custom_id`, the result must not be longer than 100 characters.
+/// ```
+/// # use slashook::structs::components::{CustomId, Button};
+/// let custom_id = CustomId::new("search", "page").with_segment(5);
+/// assert_eq!(custom_id.to_string(), "search/page/5");
+///
+/// let parsed = CustomId::parse("search/page/5").unwrap();
+/// assert_eq!(parsed.command(), "search");
+/// assert_eq!(parsed.id(), "page");
+/// assert_eq!(parsed.segments(), ["page", "5"]);
+///
+/// let button = Button::new().set_custom_id(custom_id);
+/// assert_eq!(button.custom_id, Some(String::from("search/page/5")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomId {
+  command: String,
+  segments: Vec<String>
+}
+
 /// An Action Row component
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ActionRow {
   #[serde(rename = "type")]
   component_type: ComponentType,
+  /// Optional identifier for the component, unique within the message. Discord generates one if not set
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<i32>,
   /// Components inside this row
   pub components: Vec<Component>
 }
@@ -81,6 +121,9 @@ pub struct ActionRow {
 pub struct Button {
   #[serde(rename = "type")]
   component_type: ComponentType,
+  /// Optional identifier for the component, unique within the message. Discord generates one if not set
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<i32>,
   /// One of [button styles](ButtonStyle)
   pub style: ButtonStyle,
   /// Text that appears on the button, max 80 characters
@@ -124,6 +167,9 @@ pub enum ButtonStyle {
 pub struct SelectMenu {
   #[serde(rename = "type")]
   component_type: ComponentType,
+  /// Optional identifier for the component, unique within the message. Discord generates one if not set
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<i32>,
   /// A developer-defined identifier for the select menu, max 100 characters
   pub custom_id: String,
   /// Specified choices in a select menu (only required and available for string selects; max 25
@@ -202,6 +248,9 @@ pub enum DefaultValueType {
 pub struct TextInput {
   #[serde(rename = "type")]
   component_type: ComponentType,
+  /// Optional identifier for the component, unique within the message. Discord generates one if not set
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<i32>,
   /// A developer-defined identifier for the input, max 100 characters
   pub custom_id: String,
   /// The [Text Input Style](TextInputStyle)
@@ -223,11 +272,12 @@ pub struct TextInput {
 }
 
 /// Discord Text Input Styles
-#[derive(Serialize_repr, Deserialize_repr, Clone, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Clone, Debug, Default)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
 pub enum TextInputStyle {
   /// A single-line input
+  #[default]
   SHORT = 1,
   /// A multi-line input
   PARAGRAPH = 2
@@ -343,6 +393,284 @@ impl Components {
     }
     self
   }
+
+  /// Assigns sequential unique `id`s to every component that doesn't have one yet so they can be referred to later.\
+  /// Ids that have already been set are kept as they are and skipped when assigning.
+  /// ```
+  /// # use slashook::structs::components::{Components, Component, Button};
+  /// let components = Components::new()
+  ///   .add_button(Button::new())
+  ///   .add_button(Button::new())
+  ///   .assign_ids();
+  /// let Component::ActionRow(row) = &components.0[0] else { unreachable!() };
+  /// assert_eq!(row.id, Some(1));
+  /// assert_eq!(row.components[0].id(), Some(2));
+  /// assert_eq!(row.components[1].id(), Some(3));
+  /// ```
+  pub fn assign_ids(mut self) -> Self {
+    fn collect(components: &[Component], used: &mut HashSet<i32>) {
+      for component in components.iter() {
+        if let Some(id) = component.id() {
+          used.insert(id);
+        }
+        if let Component::ActionRow(row) = component {
+          collect(&row.components, used);
+        }
+      }
+    }
+
+    fn assign(components: &mut [Component], used: &mut HashSet<i32>, next: &mut i32) {
+      for component in components.iter_mut() {
+        if let Some(id @ None) = component.id_mut() {
+          while used.contains(next) {
+            *next += 1;
+          }
+          *id = Some(*next);
+          used.insert(*next);
+        }
+        if let Component::ActionRow(row) = component {
+          assign(&mut row.components, used, next);
+        }
+      }
+    }
+
+    let mut used = HashSet::new();
+    collect(&self.0, &mut used);
+    assign(&mut self.0, &mut used, &mut 1);
+    self
+  }
+
+  /// Iterates over all interactive components depth-first, descending into any containers
+  /// ```
+  /// # use slashook::structs::components::{Components, Button, TextInput};
+  /// let components = Components::new()
+  ///   .add_button(Button::new().set_id("example_button", "first"))
+  ///   .add_row()
+  ///   .add_text_input(TextInput::new().set_id("name"));
+  /// assert_eq!(components.iter().count(), 2);
+  /// ```
+  pub fn iter(&self) -> impl Iterator<Item = &Component> {
+    ComponentIter { stack: vec![self.0.iter()] }
+  }
+
+  /// Finds a component by its `custom_id`, searching inside containers
+  /// ```
+  /// # use slashook::structs::components::{Components, Component, Button, TextInput};
+  /// let components = Components::new()
+  ///   .add_text_input(TextInput::new().set_id("name"))
+  ///   .add_row()
+  ///   .add_text_input(TextInput::new().set_id("age"));
+  /// let Some(Component::TextInput(input)) = components.find_by_custom_id("age") else { unreachable!() };
+  /// assert_eq!(input.custom_id, "age");
+  /// assert!(components.find_by_custom_id("missing").is_none());
+  /// ```
+  pub fn find_by_custom_id(&self, custom_id: &str) -> Option<&Component> {
+    self.iter().find(|c| c.custom_id() == Some(custom_id))
+  }
+
+  /// Same as [`find_by_custom_id`](Components::find_by_custom_id) but returns a mutable reference
+  /// ```
+  /// # use slashook::structs::components::{Components, Component, Button};
+  /// let mut components = Components::new()
+  ///   .add_button(Button::new().set_id("example_button", "toggle"));
+  /// if let Some(Component::Button(button)) = components.find_by_custom_id_mut("example_button/toggle") {
+  ///   button.disabled = Some(true);
+  /// }
+  /// ```
+  pub fn find_by_custom_id_mut(&mut self, custom_id: &str) -> Option<&mut Component> {
+    fn find<'a>(components: &'a mut [Component], custom_id: &str) -> Option<&'a mut Component> {
+      for component in components.iter_mut() {
+        match component {
+          Component::ActionRow(row) => {
+            if let Some(found) = find(&mut row.components, custom_id) {
+              return Some(found);
+            }
+          },
+          _ if component.custom_id() == Some(custom_id) => return Some(component),
+          _ => {}
+        }
+      }
+      None
+    }
+
+    find(&mut self.0, custom_id)
+  }
+
+  /// Checks the layout against Discord's nesting rules without contacting Discord.\
+  /// There can be up to 5 action rows at the top level and nothing else. Action rows can't contain other action rows
+  /// and hold either up to 5 buttons or a single select menu or text input.
+  /// Empty action rows are ignored since they are dropped before sending.
+  /// ```
+  /// # use slashook::structs::components::{Components, Component, ActionRow, Button, SelectMenu, SelectMenuType};
+  /// let components = Components::new()
+  ///   .add_button(Button::new())
+  ///   .add_row()
+  ///   .add_select_menu(SelectMenu::new(SelectMenuType::USER));
+  /// assert!(components.validate().is_ok());
+  ///
+  /// let mut outer = ActionRow::new();
+  /// outer.components.push(Component::ActionRow(ActionRow::new()));
+  /// let nested = Components(vec![Component::ActionRow(outer)]);
+  /// assert_eq!(nested.validate().unwrap_err().0, "Action row 0 contains another action row, action rows can't be nested");
+  ///
+  /// let loose = Components(vec![Component::Button(Box::new(Button::new()))]);
+  /// assert!(loose.validate().is_err());
+  /// ```
+  pub fn validate(&self) -> Result<(), ValidationError> {
+    let rows: Vec<&Component> = self.0.iter()
+      .filter(|c| !matches!(c, Component::ActionRow(row) if row.components.is_empty()))
+      .collect();
+    validate_layout(&rows)
+  }
+}
+
+pub(crate) fn validate_layout(components: &[&Component]) -> Result<(), ValidationError> {
+  if components.len() > 5 {
+    return Err(ValidationError(format!("There can be up to 5 action rows, found {}", components.len())));
+  }
+
+  for (i, component) in components.iter().enumerate() {
+    let row = match component {
+      Component::ActionRow(row) => row,
+      Component::Unknown => continue,
+      _ => return Err(ValidationError(format!("Component {} must be an action row", i)))
+    };
+
+    if row.components.is_empty() {
+      return Err(ValidationError(format!("Action row {} is empty", i)));
+    }
+
+    let mut buttons = 0;
+    let mut full_width = 0;
+    for child in row.components.iter() {
+      match child {
+        Component::ActionRow(_) => return Err(ValidationError(format!("Action row {} contains another action row, action rows can't be nested", i))),
+        Component::Button(_) => buttons += 1,
+        Component::SelectMenu(_) | Component::TextInput(_) => full_width += 1,
+        Component::Unknown => {}
+      }
+    }
+
+    if buttons > 5 || full_width > 1 || (full_width > 0 && buttons > 0) {
+      return Err(ValidationError(format!("Action row {} can contain up to 5 buttons or a single select menu or text input", i)));
+    }
+  }
+
+  Ok(())
+}
+
+impl CustomId {
+  /// Creates a new custom_id for a command with an id
+  pub fn new<T: ToString, U: ToString>(command: T, id: U) -> Self {
+    Self {
+      command: command.to_string(),
+      segments: vec![id.to_string()]
+    }
+  }
+
+  /// Adds a segment to the end of the custom_id
+  pub fn with_segment<T: ToString>(mut self, segment: T) -> Self {
+    self.segments.push(segment.to_string());
+    self
+  }
+
+  /// Parses a full custom_id in the `command/id` format. Returns `None` if there's no `/` in it.
+  pub fn parse(custom_id: &str) -> Option<Self> {
+    let (command, rest) = custom_id.split_once('/')?;
+    Some(Self {
+      command: command.to_string(),
+      segments: rest.split('/').map(String::from).collect()
+    })
+  }
+
+  /// The name of the command the custom_id routes to
+  pub fn command(&self) -> &str {
+    &self.command
+  }
+
+  /// The first segment after the command
+  pub fn id(&self) -> &str {
+    &self.segments[0]
+  }
+
+  /// All of the segments after the command
+  pub fn segments(&self) -> &[String] {
+    &self.segments
+  }
+}
+
+impl fmt::Display for CustomId {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}/{}", self.command, self.segments.join("/"))
+  }
+}
+
+impl From<CustomId> for String {
+  fn from(custom_id: CustomId) -> Self {
+    custom_id.to_string()
+  }
+}
+
+impl From<Components> for Vec<Component> {
+  /// Converts the components into a list that can be sent to Discord, dropping any action rows that were left empty
+  fn from(components: Components) -> Self {
+    components.0.into_iter()
+      .filter(|c| !matches!(c, Component::ActionRow(row) if row.components.is_empty()))
+      .collect()
+  }
+}
+
+struct ComponentIter<'a> {
+  stack: Vec<std::slice::Iter<'a, Component>>
+}
+
+impl<'a> Iterator for ComponentIter<'a> {
+  type Item = &'a Component;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let Some(iter) = self.stack.last_mut() {
+      match iter.next() {
+        Some(Component::ActionRow(row)) => self.stack.push(row.components.iter()),
+        Some(Component::Unknown) => continue,
+        Some(component) => return Some(component),
+        None => { self.stack.pop(); }
+      }
+    }
+    None
+  }
+}
+
+impl Component {
+  /// Returns the `id` of the component if it has one
+  pub fn id(&self) -> Option<i32> {
+    match self {
+      Self::ActionRow(row) => row.id,
+      Self::Button(button) => button.id,
+      Self::SelectMenu(select_menu) => select_menu.id,
+      Self::TextInput(text_input) => text_input.id,
+      Self::Unknown => None
+    }
+  }
+
+  /// Returns the `custom_id` of the component if it has one
+  pub fn custom_id(&self) -> Option<&str> {
+    match self {
+      Self::Button(button) => button.custom_id.as_deref(),
+      Self::SelectMenu(select_menu) => Some(&select_menu.custom_id),
+      Self::TextInput(text_input) => Some(&text_input.custom_id),
+      Self::ActionRow(_) | Self::Unknown => None
+    }
+  }
+
+  fn id_mut(&mut self) -> Option<&mut Option<i32>> {
+    match self {
+      Self::ActionRow(row) => Some(&mut row.id),
+      Self::Button(button) => Some(&mut button.id),
+      Self::SelectMenu(select_menu) => Some(&mut select_menu.id),
+      Self::TextInput(text_input) => Some(&mut text_input.id),
+      Self::Unknown => None
+    }
+  }
 }
 
 impl ActionRow {
@@ -350,6 +678,7 @@ impl ActionRow {
   pub fn new() -> Self {
     Self {
       component_type: ComponentType::ACTION_ROW,
+      id: None,
       components: Vec::new()
     }
   }
@@ -372,6 +701,7 @@ impl Button {
   pub fn new() -> Self {
     Self {
       component_type: ComponentType::BUTTON,
+      id: None,
       style: ButtonStyle::PRIMARY,
       label: None,
       emoji: None,
@@ -433,6 +763,12 @@ impl Button {
     self
   }
 
+  /// Set the custom_id for a button from a [CustomId], for when it carries more segments than just an id
+  pub fn set_custom_id(mut self, custom_id: CustomId) -> Self {
+    self.custom_id = Some(custom_id.into());
+    self
+  }
+
   /// Set the SKU for a premium-style button
   /// ```
   /// # use slashook::structs::components::{Button, ButtonStyle};
@@ -477,6 +813,7 @@ impl SelectMenu {
   pub fn new(menu_type: SelectMenuType) -> Self {
     Self {
       component_type: menu_type.into(),
+      id: None,
       custom_id: String::from(""),
       disabled: Some(false),
       options: None,
@@ -507,6 +844,12 @@ impl SelectMenu {
     self
   }
 
+  /// Set the custom_id for a select menu from a [CustomId], for when it carries more segments than just an id
+  pub fn set_custom_id(mut self, custom_id: CustomId) -> Self {
+    self.custom_id = custom_id.into();
+    self
+  }
+
   /// Set the disabled state of the select menu
   /// ```
   /// # use slashook::structs::components::{SelectMenu, SelectMenuType};
@@ -658,6 +1001,7 @@ impl TextInput {
   pub fn new() -> Self {
     Self {
       component_type: ComponentType::TEXT_INPUT,
+      id: None,
       custom_id: String::from(""),
       style: TextInputStyle::SHORT,
       label: String::from(""),
@@ -796,12 +1140,6 @@ impl Default for TextInput {
   }
 }
 
-impl Default for TextInputStyle {
-  fn default() -> Self {
-    Self::SHORT
-  }
-}
-
 impl From<SelectMenuType> for ComponentType {
   fn from(menu_type: SelectMenuType) -> Self {
     match menu_type {
@@ -10,7 +10,7 @@
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc, TimeZone};
 use std::convert::TryInto;
-use super::utils::Color;
+use super::utils::{Color, File};
 
 /// Discord Embed Object
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -49,11 +49,14 @@ pub struct Embed {
 pub struct EmbedThumbnail {
   /// Source url of thumbnail (only supports http(s) and attachments)
   pub url: String,
-  /// A proxied url of the thumbnail
+  /// A proxied url of the thumbnail, only received from Discord
+  #[serde(default, skip_serializing)]
   pub proxy_url: Option<String>,
-  /// Height of thumbnail
+  /// Height of thumbnail, only received from Discord
+  #[serde(default, skip_serializing)]
   pub height: Option<i64>,
-  /// Width of thumbnail
+  /// Width of thumbnail, only received from Discord
+  #[serde(default, skip_serializing)]
   pub width: Option<i64>
 }
 
@@ -75,11 +78,14 @@ pub struct EmbedVideo {
 pub struct EmbedImage {
   /// Source url of image (only supports http(s) and attachments)
   pub url: String,
-  /// A proxied url of the image
+  /// A proxied url of the image, only received from Discord
+  #[serde(default, skip_serializing)]
   pub proxy_url: Option<String>,
-  /// Height of image
+  /// Height of image, only received from Discord
+  #[serde(default, skip_serializing)]
   pub height: Option<i64>,
-  /// Width of image
+  /// Width of image, only received from Discord
+  #[serde(default, skip_serializing)]
   pub width: Option<i64>
 }
 
@@ -101,7 +107,8 @@ pub struct EmbedAuthor {
   pub url: Option<String>,
   /// Url of author icon (only supports http(s) and attachments)
   pub icon_url: Option<String>,
-  /// A proxied url of author icon
+  /// A proxied url of author icon, only received from Discord
+  #[serde(default, skip_serializing)]
   pub proxy_icon_url: Option<String>
 }
 
@@ -112,7 +119,8 @@ pub struct EmbedFooter {
   pub text: String,
   /// Url of footer icon (only supports http(s) and attachments)
   pub icon_url: Option<String>,
-  /// A proxied url of footer icon
+  /// A proxied url of footer icon, only received from Discord
+  #[serde(default, skip_serializing)]
   pub proxy_icon_url: Option<String>
 }
 
@@ -235,6 +243,24 @@ impl Embed {
   ///   .set_image("https://canary.discord.com/assets/7c8f476123d28d103efe381543274c25.png");
   /// assert_eq!(embed.image.unwrap().url, String::from("https://canary.discord.com/assets/7c8f476123d28d103efe381543274c25.png"));
   /// ```
+  /// The proxy url and dimensions Discord fills in are read back but never sent, so a received embed can be edited and sent again.
+  /// ```
+  /// # use slashook::structs::embeds::Embed;
+  /// let received: Embed = serde_json::from_value(serde_json::json!({
+  ///   "type": "rich",
+  ///   "image": {
+  ///     "url": "https://example.com/cat.png",
+  ///     "proxy_url": "https://media.discordapp.net/external/cat.png",
+  ///     "width": 640,
+  ///     "height": 480
+  ///   }
+  /// })).unwrap();
+  /// assert_eq!(received.image.as_ref().unwrap().width, Some(640));
+  ///
+  /// let edited = received.set_title("A cat");
+  /// let json = serde_json::to_value(&edited).unwrap();
+  /// assert_eq!(json["image"], serde_json::json!({ "url": "https://example.com/cat.png" }));
+  /// ```
   pub fn set_image<T: ToString>(mut self, url: T) -> Self {
     self.image = Some(EmbedImage {
       url: url.to_string(),
@@ -245,6 +271,20 @@ impl Embed {
     self
   }
 
+  /// Set the image of the embed to a file uploaded with the same message.\
+  /// The image links to the file with an `attachment://` url, so the file has to be added to the message as well.
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::{embeds::Embed, utils::File};
+  /// let file = File::new("cat.png", vec![0x89, 0x50, 0x4e, 0x47]);
+  /// let embed = Embed::new().set_image_from_file(&file);
+  /// assert_eq!(embed.image.as_ref().unwrap().url, "attachment://cat.png");
+  /// let response = MessageResponse::from(embed).add_file(file);
+  /// ```
+  pub fn set_image_from_file(self, file: &File) -> Self {
+    self.set_image(format!("attachment://{}", file.filename))
+  }
+
   /// Set the thumbnail of the embed
   /// ```
   /// # use slashook::structs::embeds::Embed;
@@ -279,25 +319,52 @@ impl Embed {
     self
   }
 
-  /// Add a field to the embed. An embed can have up to 25 fields.
+  /// Add a field to the embed. An embed can have up to 25 fields.\
+  /// This keeps its three arguments so existing code keeps compiling, see [`add_field_from`](Embed::add_field_from) for adding a tuple or an [EmbedField].
   /// ```
   /// # use slashook::structs::embeds::Embed;
   /// let embed = Embed::new()
   ///   .add_field("Field title", "Field description", false);
   /// assert_eq!(embed.fields.unwrap()[0].name, String::from("Field title"));
   /// ```
-  pub fn add_field<T: ToString, U: ToString>(mut self, name: T, value: U, inline: bool) -> Self {
-    let field = EmbedField {
-      name: name.to_string(),
-      value: value.to_string(),
-      inline: Some(inline)
-    };
-    if self.fields.is_none() {
-      self.fields = Some(vec![field]);
-    } else if let Some(mut fields) = self.fields {
-      fields.push(field);
-      self.fields = Some(fields);
-    }
+  pub fn add_field<T: ToString, U: ToString>(self, name: T, value: U, inline: bool) -> Self {
+    self.add_field_from((name, value, inline))
+  }
+
+  /// Add a field from anything that converts into an [EmbedField], such as a `(name, value)` or `(name, value, inline)` tuple
+  /// ```
+  /// # use slashook::structs::embeds::Embed;
+  /// let embed = Embed::new()
+  ///   .add_field_from(("Status", "Online", true))
+  ///   .add_field_from(("Uptime", "3 days"));
+  /// let fields = embed.fields.unwrap();
+  /// assert_eq!(fields[0].inline, Some(true));
+  /// assert_eq!(fields[1].inline, None);
+  /// ```
+  pub fn add_field_from<F: Into<EmbedField>>(mut self, field: F) -> Self {
+    let mut fields = self.fields.unwrap_or_default();
+    fields.push(field.into());
+    self.fields = Some(fields);
+    self
+  }
+
+  /// Add multiple fields to the embed. Fields can be constructed from `(name, value)` or `(name, value, inline)` tuples.
+  /// ```
+  /// # use slashook::structs::embeds::Embed;
+  /// # use std::collections::BTreeMap;
+  /// let mut stats = BTreeMap::new();
+  /// stats.insert("Servers", 42);
+  /// stats.insert("Users", 1337);
+  /// let embed = Embed::new()
+  ///   .add_fields(stats.iter().map(|(name, value)| (name, value, true)));
+  /// let fields = embed.fields.unwrap();
+  /// assert_eq!(fields.len(), 2);
+  /// assert_eq!(fields[1].value, String::from("1337"));
+  /// ```
+  pub fn add_fields<T: Into<EmbedField>, I: IntoIterator<Item = T>>(mut self, fields: I) -> Self {
+    let mut existing = self.fields.unwrap_or_default();
+    existing.extend(fields.into_iter().map(|f| f.into()));
+    self.fields = Some(existing);
     self
   }
 }
@@ -307,3 +374,23 @@ impl Default for Embed {
     Self::new()
   }
 }
+
+impl<T: ToString, U: ToString> From<(T, U, bool)> for EmbedField {
+  fn from((name, value, inline): (T, U, bool)) -> Self {
+    Self {
+      name: name.to_string(),
+      value: value.to_string(),
+      inline: Some(inline)
+    }
+  }
+}
+
+impl<T: ToString, U: ToString> From<(T, U)> for EmbedField {
+  fn from((name, value): (T, U)) -> Self {
+    Self {
+      name: name.to_string(),
+      value: value.to_string(),
+      inline: None
+    }
+  }
+}
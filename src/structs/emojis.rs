@@ -73,6 +73,35 @@ impl Emoji {
     }
   }
 
+  /// Parses an emoji from either a custom emoji mention (`<:name:id>` or `<a:name:id>`) or a unicode emoji
+  /// ```
+  /// # use slashook::structs::Emoji;
+  /// let emoji = Emoji::parse("<a:fastnod:837407035862679573>");
+  /// assert_eq!(emoji.id, Some(String::from("837407035862679573")));
+  /// assert_eq!(emoji.animated, Some(true));
+  /// let normal_emoji = Emoji::parse("👍");
+  /// assert_eq!(normal_emoji.name, Some(String::from("👍")));
+  /// assert_eq!(normal_emoji.id, None);
+  /// ```
+  pub fn parse<T: AsRef<str>>(emoji: T) -> Self {
+    let emoji = emoji.as_ref().trim();
+    let custom = emoji.strip_prefix('<')
+      .and_then(|e| e.strip_suffix('>'))
+      .and_then(|e| {
+        let (animated, rest) = match e.strip_prefix('a') {
+          Some(rest) => (true, rest),
+          None => (false, e)
+        };
+        let (name, id) = rest.strip_prefix(':')?.split_once(':')?;
+        Some((id, name, animated))
+      });
+
+    match custom {
+      Some((id, name, animated)) => Self::new_custom_emoji(id, name, animated),
+      None => Self::new_standard_emoji(emoji)
+    }
+  }
+
   /// Returns a string representing an emoji mention. Returns just the unicode emoji if not custom.
   /// ```
   /// # use slashook::structs::Emoji;
@@ -102,3 +131,15 @@ impl Emoji {
     }
   }
 }
+
+impl From<&str> for Emoji {
+  fn from(s: &str) -> Self {
+    Self::parse(s)
+  }
+}
+
+impl From<String> for Emoji {
+  fn from(s: String) -> Self {
+    Self::parse(s)
+  }
+}
@@ -7,16 +7,19 @@
 
 //! Structs related to Discord guilds
 
-use serde::{Deserialize, de::Deserializer};
-use serde_repr::Deserialize_repr;
+use serde::{Deserialize, Serialize, de::Deserializer};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use super::{
   Snowflake,
   Emoji,
+  audit_logs::{AuditLog, AuditLogFetchOptions},
   Permissions,
   stickers::Sticker,
   users::User,
-  utils::Color
+  utils::{Color, ValidationError}
 };
+use crate::rest::{Rest, RestError};
+use serde_json::json;
 use chrono::{DateTime, Utc};
 use bitflags::bitflags;
 
@@ -374,12 +377,12 @@ pub struct GuildScheduledEvent {
   pub image: Option<String>,
 }
 
-/// Discord Guild Scheduled Event Privacy Level
-#[derive(Deserialize_repr, Clone, Debug)]
+/// Discord Privacy Level for guild scheduled events and stage instances
+#[derive(Deserialize_repr, Serialize_repr, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
 pub enum PrivacyLevel {
-  /// The scheduled event is only accessible to guild members
+  /// The scheduled event or stage instance is only accessible to guild members
   GUILD_ONLY = 2,
   /// Privacy level that hasn't been implemented yet
   #[serde(other)]
@@ -427,6 +430,372 @@ pub struct EntityMetadata {
   pub location: Option<String>,
 }
 
+/// Discord Ban Object
+#[derive(Deserialize, Clone, Debug)]
+pub struct Ban {
+  /// The reason for the ban
+  pub reason: Option<String>,
+  /// The banned user
+  pub user: User,
+}
+
+/// Vanity invite of a guild returned by [get_vanity_url](Guild::get_vanity_url)
+#[derive(Deserialize, Clone, Debug)]
+pub struct VanityInvite {
+  /// The vanity invite code, `None` if the guild doesn't have one set
+  pub code: Option<String>,
+  /// Number of times the vanity invite has been used
+  #[serde(default)]
+  pub uses: i64,
+}
+
+/// Options for fetching a list of bans
+#[derive(Serialize, Default, Clone, Debug)]
+pub struct BanListOptions {
+  /// Number of users to return (up to maximum 1000), defaults to 1000
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub limit: Option<i64>,
+  /// Consider only users before given user id
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub before: Option<Snowflake>,
+  /// Consider only users after given user id
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub after: Option<Snowflake>,
+}
+
+/// Partial guild returned when listing the guilds of the current user
+#[derive(Deserialize, Clone, Debug)]
+pub struct PartialGuild {
+  /// Guild id
+  pub id: Snowflake,
+  /// Guild name
+  pub name: String,
+  /// [Icon hash](https://discord.com/developers/docs/reference#image-formatting)
+  pub icon: Option<String>,
+  /// [Banner hash](https://discord.com/developers/docs/reference#image-formatting)
+  pub banner: Option<String>,
+  /// True if the user is the owner of the guild
+  #[serde(default)]
+  pub owner: bool,
+  /// Total permissions for the user in the guild (excludes overwrites and implicit permissions)
+  pub permissions: Option<Permissions>,
+  /// Enabled guild features
+  #[serde(default)]
+  pub features: Vec<String>,
+  /// Approximate number of members in this guild, returned when `with_counts` is true
+  pub approximate_member_count: Option<i64>,
+  /// Approximate number of non-offline members in this guild, returned when `with_counts` is true
+  pub approximate_presence_count: Option<i64>,
+}
+
+/// Options for fetching the guilds of the current user
+#[derive(Serialize, Default, Clone, Debug)]
+pub struct CurrentUserGuildsOptions {
+  /// Get guilds before this guild ID
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub before: Option<Snowflake>,
+  /// Get guilds after this guild ID
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub after: Option<Snowflake>,
+  /// Max number of guilds to return (1-200), defaults to 200
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub limit: Option<i64>,
+  /// Include approximate member and presence counts in response
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub with_counts: Option<bool>,
+}
+
+impl Guild {
+  /// Fetches the audit log of a guild
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::{guilds::Guild, audit_logs::{AuditLogFetchOptions, AuditLogEvent}};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let options = AuditLogFetchOptions::new().set_action_type(AuditLogEvent::MEMBER_BAN_ADD).set_limit(10);
+  /// let audit_log = Guild::fetch_audit_log(&input.rest, input.guild_id.unwrap(), options).await?;
+  /// for entry in audit_log.audit_log_entries {
+  ///   println!("{:?} banned {:?}", entry.user_id, entry.target_id);
+  /// }
+  /// # }
+  /// ```
+  pub async fn fetch_audit_log<T: ToString>(rest: &Rest, guild_id: T, options: AuditLogFetchOptions) -> Result<AuditLog, RestError> {
+    rest.get_query(format!("guilds/{}/audit-logs", guild_id.to_string()), options).await
+  }
+
+  /// Fetches a list of bans in a guild
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::guilds::{Guild, BanListOptions};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let options = BanListOptions::new().set_limit(100);
+  /// let bans = Guild::list_bans(&input.rest, input.guild_id.unwrap(), options).await?;
+  /// # }
+  /// ```
+  pub async fn list_bans<T: ToString>(rest: &Rest, guild_id: T, options: BanListOptions) -> Result<Vec<Ban>, RestError> {
+    rest.get_query(format!("guilds/{}/bans", guild_id.to_string()), options).await
+  }
+
+  /// Fetches the ban for a user in a guild. Returns `None` if the user isn't banned.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::guilds::Guild;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// if let Some(ban) = Guild::get_ban(&input.rest, input.guild_id.unwrap(), "189365411036807168").await? {
+  ///   res.send_message(format!("Banned for: {}", ban.reason.unwrap_or_default())).await?;
+  /// }
+  /// # }
+  /// ```
+  pub async fn get_ban<T: ToString, U: ToString>(rest: &Rest, guild_id: T, user_id: U) -> Result<Option<Ban>, RestError> {
+    match rest.get(format!("guilds/{}/bans/{}", guild_id.to_string(), user_id.to_string())).await {
+      Ok(ban) => Ok(Some(ban)),
+      // Unknown Ban, other 404s such as an unknown guild are real errors
+      Err(err) if err.error_code() == Some(10026) => Ok(None),
+      Err(err) => Err(err)
+    }
+  }
+
+  /// Fetches the vanity invite of a guild along with its use count
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::guilds::Guild;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let vanity = Guild::get_vanity_url(&input.rest, input.guild_id.unwrap()).await?;
+  /// if let Some(code) = vanity.code {
+  ///   res.send_message(format!("discord.gg/{} has been used {} times", code, vanity.uses)).await?;
+  /// }
+  /// # }
+  /// ```
+  pub async fn get_vanity_url<T: ToString>(rest: &Rest, guild_id: T) -> Result<VanityInvite, RestError> {
+    rest.get(format!("guilds/{}/vanity-url", guild_id.to_string())).await
+  }
+
+  /// Fetches a page of guilds the current user is in
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::guilds::{Guild, CurrentUserGuildsOptions};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let mut guilds = Vec::new();
+  /// let mut options = CurrentUserGuildsOptions::new().set_limit(200);
+  /// loop {
+  ///   let page = Guild::list_current(&input.rest, options.clone()).await?;
+  ///   let Some(last) = page.last() else { break };
+  ///   options = options.set_after(&last.id);
+  ///   let full = page.len() == 200;
+  ///   guilds.extend(page);
+  ///   if !full { break }
+  /// }
+  /// res.send_message(format!("I'm in {} guilds", guilds.len())).await?;
+  /// # }
+  /// ```
+  pub async fn list_current(rest: &Rest, options: CurrentUserGuildsOptions) -> Result<Vec<PartialGuild>, RestError> {
+    rest.get_query(String::from("users/@me/guilds"), options).await
+  }
+
+  /// Changes the nickname of the current user in a guild. `None` removes the nickname.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::guilds::Guild;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// Guild::set_my_nickname(&input.rest, input.guild_id.unwrap(), Some("Helper"), Some("Renamed with /rename")).await?;
+  /// # }
+  /// ```
+  pub async fn set_my_nickname<T: ToString>(rest: &Rest, guild_id: T, nick: Option<&str>, reason: Option<&str>) -> Result<GuildMember, RestError> {
+    rest.patch_with_reason(format!("guilds/{}/members/@me", guild_id.to_string()), json!({ "nick": nick }), reason).await
+  }
+
+  /// Leaves a guild
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::guilds::Guild;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// res.send_message("Goodbye!").await?;
+  /// Guild::leave(&input.rest, input.guild_id.unwrap()).await?;
+  /// # }
+  /// ```
+  pub async fn leave<T: ToString>(rest: &Rest, guild_id: T) -> Result<(), RestError> {
+    rest.delete(format!("users/@me/guilds/{}", guild_id.to_string())).await
+  }
+}
+
+impl GuildMember {
+  /// Fetches a member of a guild. Returns `None` if the user isn't a member of the guild.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::guilds::GuildMember;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// match GuildMember::fetch(&input.rest, input.guild_id.unwrap(), "189365411036807168").await? {
+  ///   Some(member) => res.send_message(format!("They have {} roles", member.roles.len())).await?,
+  ///   None => res.send_message("They aren't in this server").await?
+  /// };
+  /// # }
+  /// ```
+  pub async fn fetch<T: ToString, U: ToString>(rest: &Rest, guild_id: T, user_id: U) -> Result<Option<Self>, RestError> {
+    match rest.get(format!("guilds/{}/members/{}", guild_id.to_string(), user_id.to_string())).await {
+      Ok(member) => Ok(Some(member)),
+      // Unknown Member, other 404s such as an unknown guild or user are real errors
+      Err(err) if err.error_code() == Some(10007) => Ok(None),
+      Err(err) => Err(err)
+    }
+  }
+
+  /// Attaches a user to the member if it doesn't already have one.\
+  /// Members in resolved data don't include the user, which is provided separately.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let user = input.args.get("user").unwrap().as_user().unwrap();
+  /// let resolved = input.resolved.as_ref().unwrap();
+  /// let member = resolved.members.as_ref().unwrap().get(&user.id).unwrap().clone().with_user(user.clone());
+  /// res.send_message(format!("Hello {}", member.display_name().unwrap())).await?;
+  /// # }
+  /// ```
+  pub fn with_user(mut self, user: User) -> Self {
+    if self.user.is_none() {
+      self.user = Some(user);
+    }
+    self
+  }
+
+  fn user_id(&self) -> Result<&Snowflake, ValidationError> {
+    self.user.as_ref().map(|u| &u.id).ok_or_else(|| ValidationError(String::from("Guild member has no user")))
+  }
+
+  /// Times out the member until the given time. The time can be up to 28 days in the future.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::chrono::{Utc, TimeDelta};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let member = input.target_member.unwrap();
+  /// member.timeout_until(&input.rest, input.guild_id.unwrap(), Utc::now() + TimeDelta::hours(1)).await?;
+  /// # }
+  /// ```
+  pub async fn timeout_until<T: ToString>(&self, rest: &Rest, guild_id: T, until: DateTime<Utc>) -> Result<GuildMember, RestError> {
+    self.set_timeout(rest, guild_id, Some(until)).await
+  }
+
+  /// Removes the member's timeout
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let member = input.target_member.unwrap();
+  /// member.remove_timeout(&input.rest, input.guild_id.unwrap()).await?;
+  /// # }
+  /// ```
+  pub async fn remove_timeout<T: ToString>(&self, rest: &Rest, guild_id: T) -> Result<GuildMember, RestError> {
+    self.set_timeout(rest, guild_id, None).await
+  }
+
+  async fn set_timeout<T: ToString>(&self, rest: &Rest, guild_id: T, until: Option<DateTime<Utc>>) -> Result<GuildMember, RestError> {
+    let path = format!("guilds/{}/members/{}", guild_id.to_string(), self.user_id()?);
+    // A timeout is only removed by null, a time in the past is treated differently by some clients
+    rest.patch(path, json!({ "communication_disabled_until": until })).await
+  }
+
+  /// Changes the nickname of the member. `None` removes the nickname.\
+  /// Use [`Guild::set_my_nickname`] for the current user.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let member = input.target_member.unwrap();
+  /// member.set_nickname(&input.rest, input.guild_id.unwrap(), None, Some("Inappropriate nickname")).await?;
+  /// # }
+  /// ```
+  pub async fn set_nickname<T: ToString>(&self, rest: &Rest, guild_id: T, nick: Option<&str>, reason: Option<&str>) -> Result<GuildMember, RestError> {
+    let path = format!("guilds/{}/members/{}", guild_id.to_string(), self.user_id()?);
+    rest.patch_with_reason(path, json!({ "nick": nick }), reason).await
+  }
+
+  /// Returns the name shown for the member in the guild, the nickname if set or the user's [display name](User::display_name) otherwise.\
+  /// None if the member has no nickname and no user.
+  pub fn display_name(&self) -> Option<&str> {
+    self.nick.as_deref().or_else(|| self.user.as_ref().map(|u| u.display_name()))
+  }
+}
+
+impl CurrentUserGuildsOptions {
+  /// Creates a new CurrentUserGuildsOptions
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets before
+  pub fn set_before<T: ToString>(mut self, before: T) -> Self {
+    self.before = Some(before.to_string());
+    self
+  }
+
+  /// Sets after
+  pub fn set_after<T: ToString>(mut self, after: T) -> Self {
+    self.after = Some(after.to_string());
+    self
+  }
+
+  /// Sets the limit
+  pub fn set_limit(mut self, limit: i64) -> Self {
+    self.limit = Some(limit);
+    self
+  }
+
+  /// Sets with_counts
+  pub fn set_with_counts(mut self, with_counts: bool) -> Self {
+    self.with_counts = Some(with_counts);
+    self
+  }
+}
+
+impl BanListOptions {
+  /// Creates a new BanListOptions
+  pub fn new() -> Self {
+    Self {
+      limit: None,
+      before: None,
+      after: None,
+    }
+  }
+
+  /// Sets the limit
+  pub fn set_limit(mut self, limit: i64) -> Self {
+    self.limit = Some(limit);
+    self
+  }
+
+  /// Sets before
+  pub fn set_before<T: ToString>(mut self, before: T) -> Self {
+    self.before = Some(before.to_string());
+    self
+  }
+
+  /// Sets after
+  pub fn set_after<T: ToString>(mut self, after: T) -> Self {
+    self.after = Some(after.to_string());
+    self
+  }
+}
+
 fn exists<'de, D: Deserializer<'de>>(d: D) -> Result<bool, D::Error> {
   serde_json::Value::deserialize(d)?;
   Ok(true)
@@ -29,7 +29,7 @@ use crate::{
 };
 
 /// Discord ApplicationCommand Object
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
 pub struct ApplicationCommand {
   /// Unique ID of command
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -77,8 +77,41 @@ pub struct ApplicationCommand {
   pub handler: Option<ApplicationCommandHandlerType>,
 }
 
+/// Options for editing an application command with [edit_global_command](ApplicationCommand::edit_global_command).
+/// Only the fields that are set are changed.
+#[derive(Serialize, Default, Clone, Debug)]
+pub struct ApplicationCommandEditOptions {
+  /// [Name of command](https://discord.com/developers/docs/interactions/application-commands#application-command-object-application-command-naming), 1-32 characters
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  /// Localization dictionary for `name` field
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name_localizations: Option<HashMap<String, String>>,
+  /// 1-100 character description
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  /// Localization dictionary for `description` field
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description_localizations: Option<HashMap<String, String>>,
+  /// The parameters for the command
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub options: Option<Vec<ApplicationCommandOption>>,
+  /// Set of [permissions](Permissions) represented as a bit set
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub default_member_permissions: Option<Permissions>,
+  /// Indicates whether the command is age-restricted
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub nsfw: Option<bool>,
+  /// [Installation context(s)](https://discord.com/developers/docs/resources/application#installation-context) where the command is available
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub integration_types: Option<Vec<IntegrationType>>,
+  /// [Interaction context(s)](InteractionContextType) where the command can be used
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub contexts: Option<Vec<InteractionContextType>>,
+}
+
 /// Discord Application Command Types
-#[derive(Serialize_repr, Deserialize_repr, Clone, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
 pub enum ApplicationCommandType {
@@ -153,7 +186,14 @@ pub struct ApplicationCommandOptionChoice {
 }
 
 /// Discord Application Intgration Types
-#[derive(Deserialize_repr, Serialize_repr, Clone, Debug)]
+/// ```
+/// # use slashook::structs::interactions::IntegrationType;
+/// assert_eq!(serde_json::to_value(IntegrationType::GUILD_INSTALL).unwrap(), 0);
+/// assert_eq!(serde_json::to_value(IntegrationType::USER_INSTALL).unwrap(), 1);
+/// let parsed: Vec<IntegrationType> = serde_json::from_str("[0, 1, 9]").unwrap();
+/// assert!(matches!(parsed[..], [IntegrationType::GUILD_INSTALL, IntegrationType::USER_INSTALL, IntegrationType::UNKNOWN]));
+/// ```
+#[derive(Deserialize_repr, Serialize_repr, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
 pub enum IntegrationType {
@@ -267,7 +307,7 @@ pub struct InteractionOption {
 }
 
 /// Discord Application Command Option Type
-#[derive(Serialize_repr, Deserialize_repr, Default, Clone, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Default, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
 pub enum InteractionOptionType {
@@ -323,7 +363,20 @@ pub enum OptionValue {
 }
 
 /// Discord Interaction Context Types
-#[derive(Deserialize_repr, Serialize_repr, Clone, Debug)]
+/// ```
+/// # use slashook::structs::interactions::InteractionContextType;
+/// assert_eq!(serde_json::to_value(InteractionContextType::GUILD).unwrap(), 0);
+/// assert_eq!(serde_json::to_value(InteractionContextType::BOT_DM).unwrap(), 1);
+/// assert_eq!(serde_json::to_value(InteractionContextType::PRIVATE_CHANNEL).unwrap(), 2);
+/// let parsed: Vec<InteractionContextType> = serde_json::from_str("[0, 1, 2, 9]").unwrap();
+/// assert!(matches!(parsed[..], [
+///   InteractionContextType::GUILD,
+///   InteractionContextType::BOT_DM,
+///   InteractionContextType::PRIVATE_CHANNEL,
+///   InteractionContextType::UNKNOWN
+/// ]));
+/// ```
+#[derive(Deserialize_repr, Serialize_repr, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
 pub enum InteractionContextType {
@@ -339,7 +392,7 @@ pub enum InteractionContextType {
 }
 
 /// Discord Entry Point Command Handler Types
-#[derive(Deserialize_repr, Serialize_repr, Clone, Debug)]
+#[derive(Deserialize_repr, Serialize_repr, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 #[allow(non_camel_case_types)]
 pub enum ApplicationCommandHandlerType {
@@ -372,12 +425,14 @@ pub enum InteractionCallbackType {
   UPDATE_MESSAGE = 7,
   APPLICATION_COMMAND_AUTOCOMPLETE_RESULT = 8,
   MODAL = 9,
+  PREMIUM_REQUIRED = 10,
   LAUNCH_ACTIVITY = 12,
 }
 
 #[doc(hidden)]
 #[derive(Serialize, Clone, Debug)]
 pub struct InteractionCallbackData {
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub tts: Option<bool>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub content: Option<String>,
@@ -399,6 +454,26 @@ pub struct InteractionCallbackData {
 
 impl ApplicationCommand {
   /// Takes a list of application commands, overwriting the existing global command list for this application.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::{Client, Config, commands::{CommandInput, CommandResponder}};
+  /// # use slashook::structs::interactions::{IntegrationType, InteractionContextType};
+  /// ##[command(
+  ///   name = "command",
+  ///   description = "An example command",
+  ///   integration_types = [IntegrationType::USER_INSTALL],
+  ///   contexts = [InteractionContextType::BOT_DM, InteractionContextType::PRIVATE_CHANNEL]
+  /// )]
+  /// fn command(_: CommandInput, res: CommandResponder) {
+  ///   res.send_message("Response");
+  /// }
+  /// # let mut client = Client::new(Config::default());
+  /// client.register_command(command);
+  /// // The payload sent to Discord when syncing
+  /// let payload = serde_json::to_value(client.preview_commands().unwrap()).unwrap();
+  /// assert_eq!(payload[0]["integration_types"], serde_json::json!([1]));
+  /// assert_eq!(payload[0]["contexts"], serde_json::json!([1, 2]));
+  /// ```
   pub async fn bulk_overwrite_global_commands<T: ToString>(rest: &Rest, application_id: T, commands: Vec<Self>) -> Result<Vec<Self>, RestError> {
     rest.put(format!("/applications/{}/commands", application_id.to_string()), commands).await
   }
@@ -407,6 +482,212 @@ impl ApplicationCommand {
   pub async fn bulk_overwrite_guild_commands<T: ToString, U: ToString>(rest: &Rest, application_id: T, guild_id: U, commands: Vec<Self>) -> Result<Vec<Self>, RestError> {
     rest.put(format!("/applications/{}/guilds/{}/commands", application_id.to_string(), guild_id.to_string()), commands).await
   }
+
+  /// Fetches the global commands for this application, including every localization
+  pub async fn fetch_global_commands<T: ToString>(rest: &Rest, application_id: T) -> Result<Vec<Self>, RestError> {
+    rest.get_query(format!("applications/{}/commands", application_id.to_string()), [("with_localizations", true)]).await
+  }
+
+  /// Fetches the guild commands for this application in the targeted guild, including every localization
+  pub async fn fetch_guild_commands<T: ToString, U: ToString>(rest: &Rest, application_id: T, guild_id: U) -> Result<Vec<Self>, RestError> {
+    rest.get_query(format!("applications/{}/guilds/{}/commands", application_id.to_string(), guild_id.to_string()), [("with_localizations", true)]).await
+  }
+
+  /// Compares the localizations of two commands, including their options and choices.\
+  /// A missing localization map and an empty one are considered the same, as Discord returns either depending on the request.
+  /// ```
+  /// # use slashook::structs::interactions::ApplicationCommand;
+  /// # use std::collections::HashMap;
+  /// let local = ApplicationCommand {
+  ///   name: String::from("hello"),
+  ///   ..Default::default()
+  /// };
+  /// let mut fetched = ApplicationCommand {
+  ///   name: String::from("hello"),
+  ///   name_localizations: Some(HashMap::new()),
+  ///   ..Default::default()
+  /// };
+  /// assert!(local.localizations_eq(&fetched));
+  ///
+  /// fetched.name_localizations = Some(HashMap::from([(String::from("fi"), String::from("hei"))]));
+  /// assert!(!local.localizations_eq(&fetched));
+  /// ```
+  pub fn localizations_eq(&self, other: &Self) -> bool {
+    localization_map_eq(&self.name_localizations, &other.name_localizations) &&
+    localization_map_eq(&self.description_localizations, &other.description_localizations) &&
+    option_localizations_eq(&self.options, &other.options)
+  }
+
+  /// Compares two commands the way Discord sees them, ignoring the fields Discord assigns (`id`, `application_id`, `guild_id` and `version`).\
+  /// Missing values are treated as their defaults: `command_type` as `CHAT_INPUT`, `description` as empty, `nsfw`, `required` and `autocomplete` as `false`,
+  /// `integration_types` as `GUILD_INSTALL`, `handler` as `APP_HANDLER` and missing lists and localizations as empty.
+  /// Integration types, contexts and channel types are compared regardless of order. Options and choices keep their order since Discord shows them in that order.
+  /// ```
+  /// # use slashook::structs::interactions::{ApplicationCommand, ApplicationCommandType, IntegrationType};
+  /// let local = ApplicationCommand {
+  ///   name: String::from("hello"),
+  ///   description: Some(String::from("Says hello")),
+  ///   ..Default::default()
+  /// };
+  /// let fetched = ApplicationCommand {
+  ///   id: Some(String::from("1234")),
+  ///   version: Some(String::from("5678")),
+  ///   command_type: Some(ApplicationCommandType::CHAT_INPUT),
+  ///   name: String::from("hello"),
+  ///   description: Some(String::from("Says hello")),
+  ///   options: Some(Vec::new()),
+  ///   nsfw: Some(false),
+  ///   integration_types: Some(vec![IntegrationType::GUILD_INSTALL]),
+  ///   ..Default::default()
+  /// };
+  /// assert!(local.semantically_eq(&fetched));
+  ///
+  /// let changed = ApplicationCommand { description: Some(String::from("Says hi")), ..fetched };
+  /// assert!(!local.semantically_eq(&changed));
+  /// ```
+  pub fn semantically_eq(&self, other: &Self) -> bool {
+    let default_integration_types = [IntegrationType::GUILD_INSTALL];
+    self.name == other.name &&
+    self.command_type.as_ref().unwrap_or(&ApplicationCommandType::CHAT_INPUT) == other.command_type.as_ref().unwrap_or(&ApplicationCommandType::CHAT_INPUT) &&
+    self.description.as_deref().unwrap_or_default() == other.description.as_deref().unwrap_or_default() &&
+    self.default_member_permissions == other.default_member_permissions &&
+    self.nsfw.unwrap_or(false) == other.nsfw.unwrap_or(false) &&
+    same_items(self.integration_types.as_deref().unwrap_or(&default_integration_types), other.integration_types.as_deref().unwrap_or(&default_integration_types)) &&
+    match (&self.contexts, &other.contexts) {
+      (Some(a), Some(b)) => same_items(a, b),
+      (a, b) => a.is_none() && b.is_none()
+    } &&
+    self.handler.as_ref().unwrap_or(&ApplicationCommandHandlerType::APP_HANDLER) == other.handler.as_ref().unwrap_or(&ApplicationCommandHandlerType::APP_HANDLER) &&
+    self.localizations_eq(other) &&
+    options_semantically_eq(&self.options, &other.options)
+  }
+
+  /// Edits a single global command. Unlike a bulk overwrite, other commands are left untouched.
+  pub async fn edit_global_command<T: ToString, U: ToString>(rest: &Rest, application_id: T, command_id: U, options: ApplicationCommandEditOptions) -> Result<Self, RestError> {
+    rest.patch(format!("applications/{}/commands/{}", application_id.to_string(), command_id.to_string()), options).await
+  }
+
+  /// Edits a single global command by its name, looking up its ID from the application's current global commands first.\
+  /// Returns `Ok(None)` if there's no command with that name.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::interactions::{ApplicationCommand, ApplicationCommandEditOptions};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let options = ApplicationCommandEditOptions::new().set_nsfw(true);
+  /// ApplicationCommand::edit_global_by_name(&input.rest, &input.application_id, "spicy", options).await?;
+  /// # }
+  /// ```
+  pub async fn edit_global_by_name<T: ToString, U: AsRef<str>>(rest: &Rest, application_id: T, name: U, options: ApplicationCommandEditOptions) -> Result<Option<Self>, RestError> {
+    let application_id = application_id.to_string();
+    let commands = Self::fetch_global_commands(rest, &application_id).await?;
+    let Some(command_id) = commands.into_iter().find(|c| c.name == name.as_ref()).and_then(|c| c.id) else {
+      return Ok(None);
+    };
+    Self::edit_global_command(rest, application_id, command_id, options).await.map(Some)
+  }
+}
+
+fn localization_map_eq(a: &Option<HashMap<String, String>>, b: &Option<HashMap<String, String>>) -> bool {
+  match (a, b) {
+    (Some(a), Some(b)) => a == b,
+    (Some(map), None) | (None, Some(map)) => map.is_empty(),
+    (None, None) => true
+  }
+}
+
+fn option_localizations_eq(a: &Option<Vec<ApplicationCommandOption>>, b: &Option<Vec<ApplicationCommandOption>>) -> bool {
+  let (a, b) = (a.as_deref().unwrap_or_default(), b.as_deref().unwrap_or_default());
+  a.len() == b.len() && a.iter().zip(b).all(|(a, b)| {
+    let (a_choices, b_choices) = (a.choices.as_deref().unwrap_or_default(), b.choices.as_deref().unwrap_or_default());
+    localization_map_eq(&a.name_localizations, &b.name_localizations) &&
+    localization_map_eq(&a.description_localizations, &b.description_localizations) &&
+    a_choices.len() == b_choices.len() &&
+    a_choices.iter().zip(b_choices).all(|(a, b)| localization_map_eq(&a.name_localizations, &b.name_localizations)) &&
+    option_localizations_eq(&a.options, &b.options)
+  })
+}
+
+fn same_items<T: PartialEq>(a: &[T], b: &[T]) -> bool {
+  a.iter().all(|item| b.contains(item)) && b.iter().all(|item| a.contains(item))
+}
+
+fn choice_value_eq(a: &Value, b: &Value) -> bool {
+  match (a, b) {
+    (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+    (a, b) => a == b
+  }
+}
+
+// Localizations are compared separately by option_localizations_eq
+fn options_semantically_eq(a: &Option<Vec<ApplicationCommandOption>>, b: &Option<Vec<ApplicationCommandOption>>) -> bool {
+  let (a, b) = (a.as_deref().unwrap_or_default(), b.as_deref().unwrap_or_default());
+  a.len() == b.len() && a.iter().zip(b).all(|(a, b)| {
+    let (a_choices, b_choices) = (a.choices.as_deref().unwrap_or_default(), b.choices.as_deref().unwrap_or_default());
+    a.option_type == b.option_type &&
+    a.name == b.name &&
+    a.description == b.description &&
+    a.required.unwrap_or(false) == b.required.unwrap_or(false) &&
+    a_choices.len() == b_choices.len() &&
+    a_choices.iter().zip(b_choices).all(|(a, b)| a.name == b.name && choice_value_eq(&a.value, &b.value)) &&
+    same_items(a.channel_types.as_deref().unwrap_or_default(), b.channel_types.as_deref().unwrap_or_default()) &&
+    a.min_value == b.min_value &&
+    a.max_value == b.max_value &&
+    a.min_length == b.min_length &&
+    a.max_length == b.max_length &&
+    a.autocomplete.unwrap_or(false) == b.autocomplete.unwrap_or(false) &&
+    options_semantically_eq(&a.options, &b.options)
+  })
+}
+
+impl ApplicationCommandEditOptions {
+  /// Creates a new empty ApplicationCommandEditOptions
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the name
+  pub fn set_name<T: ToString>(mut self, name: T) -> Self {
+    self.name = Some(name.to_string());
+    self
+  }
+
+  /// Sets the description
+  pub fn set_description<T: ToString>(mut self, description: T) -> Self {
+    self.description = Some(description.to_string());
+    self
+  }
+
+  /// Sets the options
+  pub fn set_options(mut self, options: Vec<ApplicationCommandOption>) -> Self {
+    self.options = Some(options);
+    self
+  }
+
+  /// Sets the default member permissions
+  pub fn set_default_member_permissions(mut self, permissions: Permissions) -> Self {
+    self.default_member_permissions = Some(permissions);
+    self
+  }
+
+  /// Sets whether the command is age-restricted
+  pub fn set_nsfw(mut self, nsfw: bool) -> Self {
+    self.nsfw = Some(nsfw);
+    self
+  }
+
+  /// Sets the integration types
+  pub fn set_integration_types(mut self, integration_types: Vec<IntegrationType>) -> Self {
+    self.integration_types = Some(integration_types);
+    self
+  }
+
+  /// Sets the contexts
+  pub fn set_contexts(mut self, contexts: Vec<InteractionContextType>) -> Self {
+    self.contexts = Some(contexts);
+    self
+  }
 }
 
 impl TryFrom<u8> for ApplicationCommandType {
@@ -494,6 +775,13 @@ impl From<CommandResponse> for InteractionCallback {
         }
       },
 
+      CommandResponse::PremiumRequired => {
+        InteractionCallback {
+          response_type: InteractionCallbackType::PREMIUM_REQUIRED,
+          data: None,
+        }
+      },
+
     }
   }
 }
@@ -7,9 +7,10 @@
 
 //! Structs related to Discord messages
 
+use std::collections::HashMap;
 use serde::{Deserialize, de::Deserializer};
 use serde::{Serialize, ser::Serializer};
-use serde_repr::Deserialize_repr;
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use serde_json::Value;
 use super::{
   Snowflake,
@@ -18,12 +19,12 @@ use super::{
   components::Component,
   embeds::Embed,
   emojis::Emoji,
-  guilds::GuildMember,
+  guilds::{GuildMember, Role},
   interactions::{IntegrationOwners, InteractionType, InteractionDataResolved},
   polls::{Poll, PollVoters},
   stickers::StickerItem,
   users::User,
-  utils::File,
+  utils::{File, ValidationError},
 };
 use crate::{
   rest::{Rest, RestError},
@@ -33,6 +34,34 @@ use chrono::{DateTime, Utc};
 use bitflags::bitflags;
 
 /// Discord Message Object
+///
+/// Fields that partial and system messages can leave out fall back to their defaults.
+/// ```
+/// # use slashook::structs::messages::{Message, MessageType};
+/// // A member join system message
+/// let join: Message = serde_json::from_value(serde_json::json!({
+///   "id": "1", "channel_id": "2", "type": 7, "timestamp": "2024-06-01T12:00:00.000000+00:00",
+///   "author": { "id": "3", "username": "new_member", "discriminator": "0", "avatar": null }
+/// })).unwrap();
+/// assert!(matches!(join.message_type, MessageType::USER_JOIN));
+/// assert!(join.content.is_empty() && join.mentions.is_empty() && join.embeds.is_empty());
+///
+/// // A webhook message with a numeric nonce
+/// let webhook: Message = serde_json::from_value(serde_json::json!({
+///   "id": "1", "channel_id": "2", "type": 0, "content": "Hi", "timestamp": "2024-06-01T12:00:00.000000+00:00",
+///   "author": { "id": "4", "username": "Captain Hook", "discriminator": "0000", "avatar": null, "bot": true },
+///   "webhook_id": "4", "nonce": 1250000000000000000u64, "tts": false, "pinned": false
+/// })).unwrap();
+/// assert_eq!(webhook.nonce.as_deref(), Some("1250000000000000000"));
+///
+/// // A forum thread starter message
+/// let starter: Message = serde_json::from_value(serde_json::json!({
+///   "id": "5", "channel_id": "5", "type": 0, "content": "First post", "timestamp": "2024-06-01T12:00:00.000000+00:00",
+///   "author": { "id": "3", "username": "poster", "discriminator": "0", "avatar": null },
+///   "position": 0, "flags": 0, "attachments": [], "embeds": [], "components": []
+/// })).unwrap();
+/// assert_eq!(starter.position, Some(0));
+/// ```
 #[derive(Deserialize, Clone, Debug)]
 pub struct Message {
   /// Id of the message
@@ -46,30 +75,39 @@ pub struct Message {
   /// Member properties for this message's author
   pub member: Option<GuildMember>,
   /// Contents of the message
+  #[serde(default)]
   pub content: String,
   /// When this message was sent
   pub timestamp: DateTime<Utc>,
   /// When this message was edited (or None if never)
   pub edited_timestamp: Option<DateTime<Utc>>,
   /// Whether this was a TTS message
+  #[serde(default)]
   pub tts: bool,
   /// Whether this message mentions everyone
+  #[serde(default)]
   pub mention_everyone: bool,
   /// Users specifically mentioned in the message
+  #[serde(default)]
   pub mentions: Vec<User>,
   /// Roles specifically mentioned in this message
+  #[serde(default)]
   pub mention_roles: Vec<Snowflake>,
   /// Channels specifically mentioned in this message
   pub mention_channels: Option<Vec<ChannelMention>>,
   /// Any attached files
+  #[serde(default)]
   pub attachments: Vec<Attachment>,
   /// Any embedded content
+  #[serde(default)]
   pub embeds: Vec<Embed>,
   /// Reactions to the message
   pub reactions: Option<Vec<Reaction>>,
-  /// Used for validating a message was sent
+  /// Used for validating a message was sent. Numeric nonces are converted to strings.
+  #[serde(default, deserialize_with = "string_or_number")]
   pub nonce: Option<String>,
   /// Whether this message is pinned
+  #[serde(default)]
   pub pinned: bool,
   /// If the message is generated by a webhook, this is the webhook's id
   pub webhook_id: Option<Snowflake>,
@@ -106,6 +144,65 @@ pub struct Message {
   pub poll: Option<Poll>,
 }
 
+/// A mention found in a message, see [`Message::resolved_mentions`]
+#[derive(Clone, Debug)]
+pub enum MessageMention {
+  /// A mentioned user
+  User(User),
+  /// A mentioned role, with the role if the message included it in its resolved data
+  Role {
+    /// Id of the role
+    id: Snowflake,
+    /// The role, if known
+    role: Option<Role>
+  },
+  /// A mentioned channel, with its name if the message included it
+  Channel {
+    /// Id of the channel
+    id: Snowflake,
+    /// Name of the channel, if known
+    name: Option<String>
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MentionKind {
+  User,
+  Role,
+  Channel
+}
+
+// Finds the `<@id>`, `<@!id>`, `<@&id>` and `<#id>` mentions in the content as (start, end, kind, id)
+fn find_mentions(content: &str) -> Vec<(usize, usize, MentionKind, &str)> {
+  let mut mentions = Vec::new();
+  let mut offset = 0;
+  while let Some(start) = content[offset..].find('<').map(|i| i + offset) {
+    let rest = &content[start + 1..];
+    let (kind, prefix) = if rest.starts_with("@&") {
+      (MentionKind::Role, 2)
+    } else if rest.starts_with("@!") {
+      (MentionKind::User, 2)
+    } else if rest.starts_with('@') {
+      (MentionKind::User, 1)
+    } else if rest.starts_with('#') {
+      (MentionKind::Channel, 1)
+    } else {
+      offset = start + 1;
+      continue;
+    };
+    let id_start = start + 1 + prefix;
+    let id_length = content[id_start..].find(|c: char| !c.is_ascii_digit()).unwrap_or(content.len() - id_start);
+    let id_end = id_start + id_length;
+    if id_length > 0 && content[id_end..].starts_with('>') {
+      mentions.push((start, id_end + 1, kind, &content[id_start..id_end]));
+      offset = id_end + 1;
+    } else {
+      offset = start + 1;
+    }
+  }
+  mentions
+}
+
 /// Discord Channel Mention Object
 #[derive(Deserialize, Clone, Debug)]
 pub struct ChannelMention {
@@ -272,16 +369,37 @@ pub enum MessageActivityType {
 }
 
 /// Discord Message Reference Object
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct MessageReference {
   /// Id of the originating message
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub message_id: Option<Snowflake>,
   /// Id of the originating message's channel
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub channel_id: Option<Snowflake>,
   /// Id of the originating message's guild
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub guild_id: Option<Snowflake>,
   /// When sending, whether to error if the referenced message doesn't exist instead of sending as a normal (non-reply) message, default true
-  pub fail_if_not_exists: Option<bool>
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fail_if_not_exists: Option<bool>,
+  /// [Type of reference](MessageReferenceType), defaults to a reply when not set
+  #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+  pub reference_type: Option<MessageReferenceType>,
+}
+
+/// Discord Message Reference Types
+#[derive(Deserialize_repr, Serialize_repr, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[allow(non_camel_case_types)]
+pub enum MessageReferenceType {
+  /// A standard reference used by replies
+  DEFAULT = 0,
+  /// Reference used to point to a message at a point in time
+  FORWARD = 1,
+  /// Message reference type that hasn't been implemented yet
+  #[serde(other)]
+  UNKNOWN
 }
 
 bitflags! {
@@ -313,6 +431,13 @@ bitflags! {
   }
 }
 
+impl MessageFlags {
+  /// Flags that can be set when creating a channel message
+  pub const CREATE_ALLOWED: Self = Self::SUPPRESS_EMBEDS.union(Self::SUPPRESS_NOTIFICATIONS).union(Self::IS_VOICE_MESSAGE);
+  /// Flags that can be set when editing a message
+  pub const EDIT_ALLOWED: Self = Self::SUPPRESS_EMBEDS;
+}
+
 /// Discord Message Interaction Metadata Object
 #[derive(Deserialize, Clone, Debug)]
 pub struct MessageInteractionMetadata {
@@ -396,6 +521,127 @@ pub struct ReactionFetchOptions {
 }
 
 impl Message {
+  /// Returns the users, roles and channels mentioned in the message.\
+  /// Users come from `mentions` and roles from `mention_roles`, with the role objects and channel names
+  /// filled in from `mention_channels` and the `resolved` data when the message has them. Channels are found in the content.
+  /// ```
+  /// # use slashook::structs::messages::{Message, MessageMention};
+  /// let message: Message = serde_json::from_value(serde_json::json!({
+  ///   "id": "1", "channel_id": "2", "type": 0, "timestamp": "2024-06-01T12:00:00.000000+00:00",
+  ///   "author": { "id": "3", "username": "author", "discriminator": "0", "avatar": null },
+  ///   "content": "Hi <@4>, see <#5> and ping <@&6>",
+  ///   "mentions": [{ "id": "4", "username": "friend", "discriminator": "0", "avatar": null }],
+  ///   "mention_roles": ["6"]
+  /// })).unwrap();
+  /// let mentions = message.resolved_mentions();
+  /// assert!(matches!(&mentions[0], MessageMention::User(user) if user.username == "friend"));
+  /// assert!(matches!(&mentions[1], MessageMention::Role { id, role: None } if id == "6"));
+  /// assert!(matches!(&mentions[2], MessageMention::Channel { id, name: None } if id == "5"));
+  /// ```
+  pub fn resolved_mentions(&self) -> Vec<MessageMention> {
+    let resolved = self.resolved.as_ref();
+    let mut mentions: Vec<MessageMention> = self.mentions.iter().cloned().map(MessageMention::User).collect();
+
+    for id in self.mention_roles.iter() {
+      let role = resolved.and_then(|r| r.roles.as_ref()).and_then(|roles| roles.get(id)).cloned();
+      mentions.push(MessageMention::Role { id: id.clone(), role });
+    }
+
+    let mut channel_ids: Vec<&str> = Vec::new();
+    for (_, _, kind, id) in find_mentions(&self.content) {
+      if kind == MentionKind::Channel && !channel_ids.contains(&id) {
+        channel_ids.push(id);
+      }
+    }
+    for id in channel_ids {
+      mentions.push(MessageMention::Channel { id: id.to_string(), name: self.channel_name(id) });
+    }
+
+    mentions
+  }
+
+  /// Returns the content with user, role and channel mentions replaced with their names.\
+  /// Names are taken from the data included with the message, mentions of anything the message doesn't have data for are left as they are.
+  /// ```
+  /// # use slashook::structs::messages::Message;
+  /// let message: Message = serde_json::from_value(serde_json::json!({
+  ///   "id": "1", "channel_id": "2", "type": 0, "timestamp": "2024-06-01T12:00:00.000000+00:00",
+  ///   "author": { "id": "3", "username": "author", "discriminator": "0", "avatar": null },
+  ///   "content": "Hi <@!4>, welcome to <#5>! <@7>",
+  ///   "mentions": [{ "id": "4", "username": "friend", "global_name": "Friend", "discriminator": "0", "avatar": null }],
+  ///   "mention_channels": [{ "id": "5", "guild_id": "8", "type": 0, "name": "general" }]
+  /// })).unwrap();
+  /// assert_eq!(message.clean_content(), "Hi @Friend, welcome to #general! <@7>");
+  /// ```
+  pub fn clean_content(&self) -> String {
+    let resolved = self.resolved.as_ref();
+    let mut clean = String::with_capacity(self.content.len());
+    let mut last = 0;
+
+    for (start, end, kind, id) in find_mentions(&self.content) {
+      let name = match kind {
+        MentionKind::User => self.mentions.iter().find(|u| u.id == id)
+          .or_else(|| resolved.and_then(|r| r.users.as_ref()).and_then(|users| users.get(id)))
+          .map(|user| format!("@{}", user.display_name())),
+        MentionKind::Role => resolved.and_then(|r| r.roles.as_ref()).and_then(|roles| roles.get(id))
+          .map(|role| format!("@{}", role.name)),
+        MentionKind::Channel => self.channel_name(id).map(|name| format!("#{}", name))
+      };
+      if let Some(name) = name {
+        clean.push_str(&self.content[last..start]);
+        clean.push_str(&name);
+        last = end;
+      }
+    }
+
+    clean.push_str(&self.content[last..]);
+    clean
+  }
+
+  fn channel_name(&self, id: &str) -> Option<String> {
+    self.mention_channels.as_ref().and_then(|channels| channels.iter().find(|c| c.id == id)).map(|c| c.name.clone())
+      .or_else(|| self.resolved.as_ref().and_then(|r| r.channels.as_ref()).and_then(|channels| channels.get(id)).and_then(|c| c.name.clone()))
+  }
+
+  /// Returns true if the message was sent as a response to an interaction
+  pub fn is_interaction_response(&self) -> bool {
+    self.interaction_metadata.is_some()
+  }
+
+  /// Returns the id of the original interaction response this message belongs to.\
+  /// For follow-up messages this is the original response, for the original response it's the message itself.
+  /// Returns `None` if the message isn't an interaction response.
+  pub fn original_response_id(&self) -> Option<&Snowflake> {
+    let metadata = self.interaction_metadata.as_ref()?;
+    Some(metadata.original_response_message_id.as_ref().unwrap_or(&self.id))
+  }
+
+  /// Returns the user who triggered the interaction this message responds to
+  pub fn interaction_user(&self) -> Option<&User> {
+    self.interaction_metadata.as_ref().map(|m| &m.user)
+  }
+
+  /// Returns the metadata of the interaction that opened the modal this message responds to
+  pub fn triggering_interaction(&self) -> Option<&MessageInteractionMetadata> {
+    self.interaction_metadata.as_ref()?.triggering_interaction_metadata.as_deref()
+  }
+
+  /// Returns the user who triggered the interaction that opened the modal this message responds to
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let message = input.message.unwrap();
+  /// if let Some(user) = message.triggering_interaction_user() {
+  ///   res.send_message(format!("The modal was opened by {}", user.username)).await?;
+  /// }
+  /// # }
+  /// ```
+  pub fn triggering_interaction_user(&self) -> Option<&User> {
+    self.triggering_interaction().map(|m| &m.user)
+  }
+
   /// Fetch a single message with a channel and message ID
   /// ```
   /// # #[macro_use] extern crate slashook;
@@ -422,6 +668,7 @@ impl Message {
   /// # }
   /// ```
   pub async fn fetch_many<T: ToString>(rest: &Rest, channel_id: T, options: MessageFetchOptions) -> Result<Vec<Self>, RestError> {
+    options.validate()?;
     rest.get_query(format!("channels/{}/messages", channel_id.to_string()), options).await
   }
 
@@ -436,7 +683,8 @@ impl Message {
   /// # }
   /// ```
   pub async fn create<T: ToString, U: Into<MessageResponse>>(rest: &Rest, channel_id: T, message: U) -> Result<Self, RestError> {
-    let mut message = message.into();
+    let mut message = message.into().retain_flags(MessageFlags::CREATE_ALLOWED);
+    message.validate_forward()?;
     let path = format!("channels/{}/messages", channel_id.to_string());
     if let Some(files) = message.files.take() {
       rest.post_files(path, message, files).await
@@ -445,6 +693,29 @@ impl Message {
     }
   }
 
+  /// Reply to the message. The reply is still sent as a normal message if this message has been deleted.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::messages::Message;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let msg = Message::fetch(&input.rest, "613430047285706767", "916413462467465246").await?;
+  /// msg.reply(&input.rest, "Hello to you too!").await?;
+  /// # }
+  /// ```
+  pub async fn reply<T: Into<MessageResponse>>(&self, rest: &Rest, message: T) -> Result<Self, RestError> {
+    let reference = MessageReference {
+      message_id: Some(self.id.clone()),
+      channel_id: Some(self.channel_id.clone()),
+      guild_id: self.guild_id.clone(),
+      fail_if_not_exists: Some(false),
+      reference_type: None,
+    };
+    let message = message.into().set_message_reference(reference);
+    Self::create(rest, &self.channel_id, message).await
+  }
+
   /// Edit a message
   /// ```
   /// # #[macro_use] extern crate slashook;
@@ -457,7 +728,7 @@ impl Message {
   /// # }
   /// ```
   pub async fn edit<T: Into<MessageResponse>>(&self, rest: &Rest, message: T) -> Result<Message, RestError> {
-    let mut message = message.into();
+    let mut message = message.into().retain_flags(MessageFlags::EDIT_ALLOWED);
     let path = format!("channels/{}/messages/{}", self.channel_id, self.id);
     if let Some(files) = message.files.take() {
       rest.patch_files(path, message, files).await
@@ -481,7 +752,8 @@ impl Message {
     rest.delete(format!("channels/{}/messages/{}", self.channel_id, self.id)).await
   }
 
-  /// Publish a message that was posted in an [Announcement channel](ChannelType::GUILD_ANNOUNCEMENT)
+  /// Publish a message that was posted in an [Announcement channel](ChannelType::GUILD_ANNOUNCEMENT).\
+  /// Use [`Channel::is_announcement`](super::channels::Channel::is_announcement) to check the channel first.
   /// ```
   /// # #[macro_use] extern crate slashook;
   /// # use slashook::commands::{CommandInput, CommandResponder};
@@ -511,6 +783,22 @@ impl Message {
     rest.put(format!("channels/{}/messages/{}/reactions/{}/@me", &self.channel_id, &self.id, emoji.to_url_format()), Value::Null).await
   }
 
+  /// Add a reaction to a message from anything that can be turned into an [Emoji], such as a unicode emoji or a custom emoji mention
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::messages::Message;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let msg = Message::create(&input.rest, "344581598878105605", "Hello!").await?;
+  /// msg.react(&input.rest, "👋").await?;
+  /// msg.react(&input.rest, "<:Thonk:356549630474846209>").await?;
+  /// # }
+  /// ```
+  pub async fn react<T: Into<Emoji>>(&self, rest: &Rest, emoji: T) -> Result<(), RestError> {
+    self.create_reaction(rest, &emoji.into()).await
+  }
+
   /// Remove the bot's reaction to a message
   /// ```
   /// # #[macro_use] extern crate slashook;
@@ -668,6 +956,31 @@ impl Message {
   }
 }
 
+impl MessageReference {
+  /// Creates a reference for forwarding a message.\
+  /// A forward can't be sent with any content of its own, the forwarded message is shown instead.
+  /// ```
+  /// # use slashook::commands::MessageResponse;
+  /// # use slashook::structs::messages::MessageReference;
+  /// let reference = MessageReference::new_forward("697138785317814292", "916413462467465246");
+  /// assert!(MessageResponse::from(reference).validate().is_ok());
+  /// ```
+  pub fn new_forward<T: ToString, U: ToString>(channel_id: T, message_id: U) -> Self {
+    Self {
+      message_id: Some(message_id.to_string()),
+      channel_id: Some(channel_id.to_string()),
+      guild_id: None,
+      fail_if_not_exists: None,
+      reference_type: Some(MessageReferenceType::FORWARD),
+    }
+  }
+
+  /// Returns true if this reference forwards a message instead of replying to it
+  pub fn is_forward(&self) -> bool {
+    self.reference_type == Some(MessageReferenceType::FORWARD)
+  }
+}
+
 impl Attachment {
   /// Creates an attachment object that can be used to tell discord to keep the attachment when editing.
   pub fn keep_with_id<T: ToString>(id: T) -> Self {
@@ -688,12 +1001,78 @@ impl Attachment {
     }
   }
 
+  /// Builds the attachments list for uploading files, which is done automatically when sending files.\
+  /// Attachments that are being kept are left as they are and each file gets an entry with the same index as its `files[n]` upload.
+  /// Entries with an index as their id are treated as placeholders for the file with that index and their description is used if the file doesn't have one.
+  /// ```
+  /// # use slashook::structs::{messages::Attachment, utils::File};
+  /// let kept = Attachment::keep_with_id("1234567890123456789");
+  /// let files = vec![
+  ///   File::new("first.png", vec![1]).set_description("The first image"),
+  ///   File::new("second.png", vec![2]),
+  /// ];
+  /// let mut placeholder = Attachment::keep_with_id("1");
+  /// placeholder.description = Some(String::from("The second image"));
+  ///
+  /// let attachments = Attachment::for_upload(vec![placeholder, kept], &files);
+  /// let ids: Vec<&str> = attachments.iter().map(|a| a.id.as_str()).collect();
+  /// assert_eq!(ids, ["1234567890123456789", "0", "1"]);
+  /// assert_eq!(attachments[1].filename, "first.png");
+  /// assert_eq!(attachments[1].description.as_deref(), Some("The first image"));
+  /// assert_eq!(attachments[2].filename, "second.png");
+  /// assert_eq!(attachments[2].description.as_deref(), Some("The second image"));
+  /// ```
+  pub fn for_upload(attachments: Vec<Attachment>, files: &[File]) -> Vec<Attachment> {
+    let mut placeholders = HashMap::new();
+    let mut result = Vec::with_capacity(attachments.len() + files.len());
+    for attachment in attachments {
+      match attachment.id.parse::<usize>() {
+        Ok(index) if index < files.len() => { placeholders.insert(index, attachment); },
+        _ => result.push(attachment)
+      }
+    }
+
+    for (index, file) in files.iter().enumerate() {
+      let mut upload = Self::from_file(index.to_string(), file);
+      if let Some(placeholder) = placeholders.remove(&index) {
+        upload.description = upload.description.or(placeholder.description);
+      }
+      result.push(upload);
+    }
+
+    result
+  }
+
+  /// Downloads the attachment into a [File] that can be attached to another message, keeping the filename, description and content type.\
+  /// The download has the same time and [size limits](crate::rest::DOWNLOAD_SIZE_LIMIT) as [`Rest::download`].
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder, MessageResponse};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let attachment = input.args.get("image").unwrap().as_attachment().unwrap();
+  /// let file = attachment.to_file(&input.rest).await?;
+  /// res.send_message(MessageResponse::from("Here it is again").add_file(file)).await?;
+  /// # }
+  /// ```
+  pub async fn to_file(&self, rest: &Rest) -> Result<File, RestError> {
+    let data = rest.download(&self.url).await?;
+    Ok(File {
+      filename: self.filename.clone(),
+      data,
+      description: self.description.clone(),
+      duration_secs: self.duration_secs,
+      waveform: self.waveform.clone(),
+      content_type: self.content_type.clone()
+    })
+  }
+
   pub(crate) fn from_file(id: Snowflake, file: &File) -> Self {
     Self {
       id,
-      filename: String::from(""),
+      filename: file.filename.clone(),
       description: file.description.clone(),
-      content_type: None,
+      content_type: file.content_type.clone(),
       size: 0,
       url: String::from(""),
       proxy_url: String::from(""),
@@ -824,6 +1203,29 @@ impl MessageFetchOptions {
     self.limit = Some(limit);
     self
   }
+
+  /// Checks that at most one of `around`, `before`, or `after` is set.
+  /// This is checked automatically by [fetch_many](Message::fetch_many).
+  /// ```
+  /// # use slashook::structs::messages::MessageFetchOptions;
+  /// let options = MessageFetchOptions {
+  ///   before: Some(String::from("940762083820175440")),
+  ///   after: Some(String::from("916413462467465246")),
+  ///   ..Default::default()
+  /// };
+  /// assert!(options.validate().is_err());
+  /// assert!(options.set_after("916413462467465246").validate().is_ok());
+  /// ```
+  pub fn validate(&self) -> Result<(), ValidationError> {
+    let set: Vec<&str> = [("around", &self.around), ("before", &self.before), ("after", &self.after)]
+      .into_iter()
+      .filter_map(|(name, value)| value.as_ref().map(|_| name))
+      .collect();
+    if set.len() > 1 {
+      return Err(ValidationError(format!("Only one of around, before, or after can be set, found {}", set.join(", "))));
+    }
+    Ok(())
+  }
 }
 
 impl ReactionFetchOptions {
@@ -848,6 +1250,14 @@ impl ReactionFetchOptions {
   }
 }
 
+fn string_or_number<'de, D: Deserializer<'de>>(d: D) -> Result<Option<String>, D::Error> {
+  Ok(match Option::<Value>::deserialize(d)? {
+    Some(Value::String(string)) => Some(string),
+    Some(Value::Number(number)) => Some(number.to_string()),
+    _ => None
+  })
+}
+
 impl<'de> Deserialize<'de> for MessageFlags {
   fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
     let bits = u32::deserialize(d)?;
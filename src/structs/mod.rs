@@ -12,6 +12,7 @@
 // Mods with just one struct will be exported without a category.
 
 pub mod applications;
+pub mod audit_logs;
 pub mod channels;
 pub mod components;
 pub mod embeds;
@@ -25,6 +26,7 @@ pub mod monetization;
 mod permissions;
 pub use permissions::Permissions;
 pub mod polls;
+pub mod stage_instances;
 pub mod stickers;
 pub mod users;
 pub mod utils;
@@ -216,6 +216,31 @@ impl SKU {
 }
 
 impl Entitlement {
+  /// Returns true if the entitlement currently grants access to its SKU.
+  /// Deleted, consumed, expired and not yet started entitlements are not active.
+  /// ```
+  /// # use slashook::structs::monetization::Entitlement;
+  /// let entitlement: Entitlement = serde_json::from_value(serde_json::json!({
+  ///   "id": "1019653849998299136",
+  ///   "sku_id": "1019475255913222144",
+  ///   "application_id": "1019370614521200640",
+  ///   "user_id": "771129655544643584",
+  ///   "type": 8,
+  ///   "deleted": false,
+  ///   "starts_at": "2022-09-14T17:00:18.704163+00:00",
+  ///   "ends_at": "2022-10-14T17:00:18.704163+00:00",
+  ///   "consumed": false
+  /// })).unwrap();
+  /// assert!(!entitlement.is_active());
+  /// ```
+  pub fn is_active(&self) -> bool {
+    let now = Utc::now();
+    !self.deleted
+      && !self.consumed.unwrap_or(false)
+      && self.starts_at.is_none_or(|starts_at| starts_at <= now)
+      && self.ends_at.is_none_or(|ends_at| ends_at > now)
+  }
+
   /// Lists all entitlements
   pub async fn list_entitlements<T: ToString>(rest: &Rest, application_id: T, options: ListEntitlementsOptions) -> Result<Vec<Entitlement>, RestError> {
     rest.get_query(format!("applications/{}/entitlements", application_id.to_string()), options).await
@@ -226,7 +251,19 @@ impl Entitlement {
     rest.post(format!("applications/{}/entitlements/{}/consume", application_id.to_string(), self.id), Value::Null).await
   }
 
-  /// Creates a test entitlement
+  /// Creates a test entitlement to a SKU for a user or guild. Discord doesn't require payment for it.
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::monetization::{Entitlement, EntitlementOwnerType, TestEntitlementOptions};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let options = TestEntitlementOptions::new("1234", &input.user.id, EntitlementOwnerType::User);
+  /// let entitlement = Entitlement::create_test_entitlement(&input.rest, &input.application_id, options).await?;
+  /// // Test the premium flow, then clean up
+  /// entitlement.delete_test_entitlement(&input.rest, &input.application_id).await?;
+  /// # }
+  /// ```
   pub async fn create_test_entitlement<T: ToString>(rest: &Rest, application_id: T, options: TestEntitlementOptions) -> Result<Entitlement, RestError> {
     rest.post(format!("applications/{}/entitlements", application_id.to_string()), options).await
   }
@@ -237,6 +274,17 @@ impl Entitlement {
   }
 }
 
+impl TestEntitlementOptions {
+  /// Creates a new TestEntitlementOptions for the SKU and owner
+  pub fn new<T: ToString, U: ToString>(sku_id: T, owner_id: U, owner_type: EntitlementOwnerType) -> Self {
+    Self {
+      sku_id: sku_id.to_string(),
+      owner_id: owner_id.to_string(),
+      owner_type,
+    }
+  }
+}
+
 impl Subscription {
   /// List all subscriptions containing the SKU
   pub async fn list_sku_subscriptions<T: ToString>(rest: &Rest, sku_id: T, options: ListSubscriptionOptions) -> Result<Vec<Subscription>, RestError> {
@@ -10,8 +10,16 @@
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use super::{
+  Snowflake,
   Emoji,
+  messages::Message,
   users::User,
+  utils::ValidationError,
+};
+use crate::{
+  commands::MessageResponse,
+  rest::{Rest, RestError},
+  tokio::time::{sleep, Duration},
 };
 use chrono::{DateTime, Utc};
 
@@ -104,6 +112,171 @@ pub struct PollVoters {
   pub users: Vec<User>,
 }
 
+/// A helper for running a poll from start to finish and collecting the results
+/// ```
+/// # #[macro_use] extern crate slashook;
+/// # use slashook::commands::{CommandInput, CommandResponder};
+/// # use slashook::structs::polls::{PollCreateRequest, PollSession};
+/// # use slashook::tokio::time::Duration;
+/// # #[command(name = "example", description = "An example command")]
+/// # fn example(input: CommandInput, res: CommandResponder) {
+/// let poll = PollCreateRequest::new("Pizza or pasta?")
+///   .add_answer("Pizza")
+///   .add_answer("Pasta");
+/// let session = PollSession::start(&input.rest, input.channel_id.as_ref().unwrap(), poll).await?;
+/// let results = session.end_after(&input.rest, Duration::from_secs(60)).await?;
+/// for answer in results.winners() {
+///   println!("{:?} won with {} votes", answer.text, answer.count);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PollSession {
+  /// The message containing the poll
+  pub message: Message,
+}
+
+/// The tallied results of a poll from a [PollSession]
+#[derive(Clone, Debug)]
+pub struct PollTally {
+  /// Whether the votes have been precisely counted
+  pub is_finalized: bool,
+  /// Each of the answers with their vote count, in the order they appear in the poll
+  pub answers: Vec<PollTallyAnswer>,
+}
+
+/// A single answer in a [PollTally]
+#[derive(Clone, Debug)]
+pub struct PollTallyAnswer {
+  /// The ID of the answer
+  pub answer_id: i64,
+  /// The text of the answer
+  pub text: Option<String>,
+  /// The emoji of the answer
+  pub emoji: Option<Emoji>,
+  /// The number of votes for this answer
+  pub count: i64,
+}
+
+impl PollSession {
+  const FINALIZE_ATTEMPTS: u32 = 5;
+
+  /// Posts a poll to a channel and starts a session for it
+  pub async fn start<T: ToString, U: Into<MessageResponse>>(rest: &Rest, channel_id: T, message: U) -> Result<Self, RestError> {
+    let message = Message::create(rest, channel_id, message).await?;
+    Ok(Self { message })
+  }
+
+  /// Starts a session from a message that already has a poll, for example one sent as an interaction response
+  pub fn from_message(message: Message) -> Self {
+    Self { message }
+  }
+
+  /// The id of the channel the poll was posted in
+  pub fn channel_id(&self) -> &Snowflake {
+    &self.message.channel_id
+  }
+
+  /// Fetches the current results without ending the poll. The counts may not be final.
+  pub async fn results(&self, rest: &Rest) -> Result<PollTally, RestError> {
+    let message = Message::fetch(rest, &self.message.channel_id, &self.message.id).await?;
+    Ok(PollTally::from_message(&message))
+  }
+
+  /// Ends the poll immediately and returns the results.\
+  /// Discord counts the final votes shortly after the poll ends, so the message is refetched a few times until the results are finalized.
+  /// If they still aren't, the latest counts are returned with `is_finalized` set to `false`.
+  pub async fn end(&self, rest: &Rest) -> Result<PollTally, RestError> {
+    let message = self.message.end_poll(rest).await?;
+    self.wait_for_finalized(rest, PollTally::from_message(&message)).await
+  }
+
+  async fn wait_for_finalized(&self, rest: &Rest, mut tally: PollTally) -> Result<PollTally, RestError> {
+    let mut attempt = 1;
+    while !tally.is_finalized && attempt <= Self::FINALIZE_ATTEMPTS {
+      sleep(Duration::from_secs(attempt.into())).await;
+      tally = self.results(rest).await?;
+      attempt += 1;
+    }
+
+    Ok(tally)
+  }
+
+  /// Waits for the given duration, then ends the poll and returns the results
+  pub async fn end_after(&self, rest: &Rest, duration: Duration) -> Result<PollTally, RestError> {
+    sleep(duration).await;
+    self.end(rest).await
+  }
+
+  /// Waits until the poll expires on its own and returns the results, refetching them like [`end`](Self::end) until they're finalized.\
+  /// Fails with a [ValidationError](RestError::ValidationError) if the poll has no expiry.
+  pub async fn wait_for_expiry(&self, rest: &Rest) -> Result<PollTally, RestError> {
+    let Some(expiry) = self.message.poll.as_ref().and_then(|p| p.expiry) else {
+      return Err(ValidationError(String::from("The poll has no expiry to wait for")).into())
+    };
+    if let Ok(remaining) = (expiry - Utc::now()).to_std() {
+      sleep(remaining).await;
+    }
+    // The poll has already ended so it can't be expired again, only refetched
+    let tally = self.results(rest).await?;
+    self.wait_for_finalized(rest, tally).await
+  }
+}
+
+impl PollTally {
+  /// Tallies the results of the poll on a message. Answers without any votes get a count of 0.
+  pub fn from_message(message: &Message) -> Self {
+    let Some(poll) = &message.poll else {
+      return Self { is_finalized: false, answers: Vec::new() };
+    };
+    let results = poll.results.as_ref();
+
+    let answers = poll.answers.iter().filter_map(|answer| {
+      let answer_id = answer.answer_id?;
+      let count = results
+        .and_then(|r| r.answer_counts.iter().find(|c| c.id == answer_id))
+        .map(|c| c.count)
+        .unwrap_or(0);
+      Some(PollTallyAnswer {
+        answer_id,
+        text: answer.poll_media.text.clone(),
+        emoji: answer.poll_media.emoji.clone(),
+        count,
+      })
+    }).collect();
+
+    Self {
+      is_finalized: results.is_some_and(|r| r.is_finalized),
+      answers,
+    }
+  }
+
+  /// The total number of votes across all answers
+  pub fn total_votes(&self) -> i64 {
+    self.answers.iter().map(|a| a.count).sum()
+  }
+
+  /// The answers with the most votes. Multiple answers are returned if there's a tie and none if nobody voted.
+  /// ```
+  /// # use slashook::structs::polls::{PollTally, PollTallyAnswer};
+  /// let answer = |answer_id, count| PollTallyAnswer { answer_id, text: None, emoji: None, count };
+  /// let tally = PollTally {
+  ///   is_finalized: true,
+  ///   answers: vec![answer(1, 3), answer(2, 5), answer(3, 5)],
+  /// };
+  /// assert_eq!(tally.total_votes(), 13);
+  /// let winners: Vec<i64> = tally.winners().iter().map(|a| a.answer_id).collect();
+  /// assert_eq!(winners, vec![2, 3]);
+  /// ```
+  pub fn winners(&self) -> Vec<&PollTallyAnswer> {
+    let max = self.answers.iter().map(|a| a.count).max().unwrap_or(0);
+    if max == 0 {
+      return Vec::new();
+    }
+    self.answers.iter().filter(|a| a.count == max).collect()
+  }
+}
+
 impl PollCreateRequest {
   /// Creates a new poll with a question. Defaults to default layout, no answers, 24h duration, and no multiselect.
   /// ```
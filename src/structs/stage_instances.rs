@@ -0,0 +1,131 @@
+// Copyright 2024 slashook Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Structs related to Discord stage instances
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::{
+  Snowflake,
+  guilds::PrivacyLevel,
+};
+use crate::rest::{Rest, RestError};
+
+/// Discord Stage Instance Object
+#[derive(Deserialize, Clone, Debug)]
+pub struct StageInstance {
+  /// The id of this Stage instance
+  pub id: Snowflake,
+  /// The guild id of the associated Stage channel
+  pub guild_id: Snowflake,
+  /// The id of the associated Stage channel
+  pub channel_id: Snowflake,
+  /// The topic of the Stage instance (1-120 characters)
+  pub topic: String,
+  /// The [privacy level](PrivacyLevel) of the Stage instance
+  pub privacy_level: PrivacyLevel,
+  /// The id of the scheduled event for this Stage instance
+  pub guild_scheduled_event_id: Option<Snowflake>,
+}
+
+/// Parameters for modifying a stage instance with [modify](StageInstance::modify)
+#[derive(Serialize, Default, Clone, Debug)]
+pub struct StageInstanceModifyOptions {
+  /// The topic of the Stage instance (1-120 characters)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub topic: Option<String>,
+  /// The [privacy level](PrivacyLevel) of the Stage instance
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub privacy_level: Option<PrivacyLevel>,
+}
+
+impl StageInstance {
+  /// Creates a new Stage instance associated to a Stage channel
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::{guilds::PrivacyLevel, stage_instances::StageInstance};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let stage = StageInstance::create(&input.rest, "613430047285706767", "Weekly Q&A", PrivacyLevel::GUILD_ONLY).await?;
+  /// # }
+  /// ```
+  pub async fn create<T: ToString, U: ToString>(rest: &Rest, channel_id: T, topic: U, privacy_level: PrivacyLevel) -> Result<Self, RestError> {
+    rest.post(String::from("stage-instances"), json!({
+      "channel_id": channel_id.to_string(),
+      "topic": topic.to_string(),
+      "privacy_level": privacy_level
+    })).await
+  }
+
+  /// Fetch the Stage instance associated with a Stage channel
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::stage_instances::StageInstance;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let stage = StageInstance::fetch(&input.rest, "613430047285706767").await?;
+  /// # }
+  /// ```
+  pub async fn fetch<T: ToString>(rest: &Rest, channel_id: T) -> Result<Self, RestError> {
+    rest.get(format!("stage-instances/{}", channel_id.to_string())).await
+  }
+
+  /// Edits the Stage instance
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::stage_instances::{StageInstance, StageInstanceModifyOptions};
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let stage = StageInstance::fetch(&input.rest, "613430047285706767").await?;
+  /// let options = StageInstanceModifyOptions::new().set_topic("Weekly Q&A: Part 2");
+  /// let modified_stage = stage.modify(&input.rest, options).await?;
+  /// # }
+  /// ```
+  pub async fn modify(&self, rest: &Rest, options: StageInstanceModifyOptions) -> Result<Self, RestError> {
+    rest.patch(format!("stage-instances/{}", self.channel_id), options).await
+  }
+
+  /// Deletes the Stage instance, closing the stage
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::stage_instances::StageInstance;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let stage = StageInstance::fetch(&input.rest, "613430047285706767").await?;
+  /// stage.delete(&input.rest).await?;
+  /// # }
+  /// ```
+  pub async fn delete(&self, rest: &Rest) -> Result<(), RestError> {
+    rest.delete(format!("stage-instances/{}", self.channel_id)).await
+  }
+}
+
+impl StageInstanceModifyOptions {
+  /// Creates a new empty StageInstanceModifyOptions
+  pub fn new() -> Self {
+    Self {
+      topic: None,
+      privacy_level: None,
+    }
+  }
+
+  /// Sets the topic
+  pub fn set_topic<T: ToString>(mut self, topic: T) -> Self {
+    self.topic = Some(topic.to_string());
+    self
+  }
+
+  /// Sets the privacy level
+  pub fn set_privacy_level(mut self, privacy_level: PrivacyLevel) -> Self {
+    self.privacy_level = Some(privacy_level);
+    self
+  }
+}
@@ -13,6 +13,7 @@ use super::{
   Snowflake,
   users::User,
 };
+use crate::rest::{Rest, RestError};
 
 /// Discord Sticker Object
 #[derive(Deserialize, Clone, Debug)]
@@ -82,3 +83,46 @@ pub enum StickerFormatType {
   #[serde(other)]
   UNKNOWN
 }
+
+/// Discord Sticker Pack Object
+#[derive(Deserialize, Clone, Debug)]
+pub struct StickerPack {
+  /// Id of the sticker pack
+  pub id: Snowflake,
+  /// The stickers in the pack
+  pub stickers: Vec<Sticker>,
+  /// Name of the sticker pack
+  pub name: String,
+  /// Id of the pack's SKU
+  pub sku_id: Snowflake,
+  /// Id of a sticker in the pack which is shown as the pack's icon
+  pub cover_sticker_id: Option<Snowflake>,
+  /// Description of the sticker pack
+  pub description: String,
+  /// Id of the sticker pack's [banner image](https://discord.com/developers/docs/reference#image-formatting)
+  pub banner_asset_id: Option<Snowflake>,
+}
+
+#[derive(Deserialize)]
+struct StickerPackList {
+  sticker_packs: Vec<StickerPack>
+}
+
+impl Sticker {
+  /// Fetches the sticker packs available to all users
+  /// ```
+  /// # #[macro_use] extern crate slashook;
+  /// # use slashook::commands::{CommandInput, CommandResponder};
+  /// # use slashook::structs::stickers::Sticker;
+  /// # #[command(name = "example", description = "An example command")]
+  /// # fn example(input: CommandInput, res: CommandResponder) {
+  /// let packs = Sticker::list_packs(&input.rest).await?;
+  /// let names: Vec<String> = packs.into_iter().map(|p| p.name).collect();
+  /// res.send_message(names.join(", ")).await?;
+  /// # }
+  /// ```
+  pub async fn list_packs(rest: &Rest) -> Result<Vec<StickerPack>, RestError> {
+    let list: StickerPackList = rest.get(String::from("sticker-packs")).await?;
+    Ok(list.sticker_packs)
+  }
+}
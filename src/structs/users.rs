@@ -22,6 +22,8 @@ pub struct User {
   pub username: String,
   /// The user's 4-digit discord-tag
   pub discriminator: String,
+  /// The user's display name, if it is set. For bots, this is the application name
+  pub global_name: Option<String>,
   /// The user's [avatar hash](https://discord.com/developers/docs/reference#image-formatting)
   pub avatar: Option<String>,
   /// Whether the user belongs to an OAuth2 application
@@ -113,6 +115,49 @@ impl User {
   pub fn mention(&self) -> String {
     format!("<@{}>", self.id)
   }
+
+  /// Returns the name shown for the user, the global name if set or the username otherwise
+  /// ```
+  /// # use slashook::structs::users::User;
+  /// let user: User = serde_json::from_value(serde_json::json!({
+  ///   "id": "80351110224678912",
+  ///   "username": "nelly",
+  ///   "discriminator": "0",
+  ///   "global_name": "Nelly",
+  ///   "public_flags": 131072
+  /// })).unwrap();
+  /// assert_eq!(user.display_name(), "Nelly");
+  /// assert!(user.is_bot_developer());
+  /// assert!(!user.is_active_developer());
+  /// ```
+  pub fn display_name(&self) -> &str {
+    self.global_name.as_deref().unwrap_or(&self.username)
+  }
+
+  /// Returns the public flags on the user's account, empty if they weren't included
+  pub fn public_flags(&self) -> UserFlags {
+    self.public_flags.unwrap_or(UserFlags::empty())
+  }
+
+  /// Returns true if the user is an Early Verified Bot Developer
+  pub fn is_bot_developer(&self) -> bool {
+    self.public_flags().contains(UserFlags::VERIFIED_DEVELOPER)
+  }
+
+  /// Returns true if the user is an Active Developer
+  pub fn is_active_developer(&self) -> bool {
+    self.public_flags().contains(UserFlags::ACTIVE_DEVELOPER)
+  }
+
+  /// Returns true if the user is a verified bot
+  pub fn is_verified_bot(&self) -> bool {
+    self.public_flags().contains(UserFlags::VERIFIED_BOT)
+  }
+
+  /// Returns true if the user is a Discord Certified Moderator
+  pub fn is_certified_moderator(&self) -> bool {
+    self.public_flags().contains(UserFlags::CERTIFIED_MODERATOR)
+  }
 }
 
 impl<'de> Deserialize<'de> for UserFlags {
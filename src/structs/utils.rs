@@ -10,6 +10,11 @@
 use serde::{Serialize, Deserialize};
 use crate::tokio::{fs, io::AsyncReadExt};
 use std::convert::TryFrom;
+use thiserror::Error;
+use chrono::{DateTime, Utc};
+
+/// Milliseconds since the Unix epoch of the first second of 2015, the epoch of Discord snowflakes
+pub const DISCORD_EPOCH: i64 = 1420070400000;
 
 /// Represents a color
 ///
@@ -38,9 +43,30 @@ pub struct File {
   /// The duration in seconds for a voice message
   pub duration_secs: Option<f64>,
   /// The waveform for a voice message
-  pub waveform: Option<String>
+  pub waveform: Option<String>,
+  /// The [media type](https://en.wikipedia.org/wiki/Media_type) of the file, sent with the file when uploading
+  pub content_type: Option<String>
 }
 
+/// Gets the time a snowflake was created at. Returns `None` if the snowflake isn't a valid number.
+/// ```
+/// # use slashook::structs::utils::snowflake_timestamp;
+/// let timestamp = snowflake_timestamp("175928847299117063").unwrap();
+/// assert_eq!(timestamp.timestamp_millis(), 1462015105796);
+/// assert!(snowflake_timestamp("not a snowflake").is_none());
+/// ```
+pub fn snowflake_timestamp<T: AsRef<str>>(snowflake: T) -> Option<DateTime<Utc>> {
+  let id: u64 = snowflake.as_ref().parse().ok()?;
+  DateTime::from_timestamp_millis((id >> 22) as i64 + DISCORD_EPOCH)
+}
+
+/// Error for when data doesn't pass the checks done before sending it to Discord
+///
+/// The contained string describes which rule was broken.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error("Validation failed: {0}")]
+pub struct ValidationError(pub String);
+
 impl Color {
   /// Returns a hex color code representation of the color
   /// ```
@@ -92,7 +118,8 @@ impl File {
       data: data.into(),
       description: None,
       duration_secs: None,
-      waveform: None
+      waveform: None,
+      content_type: None
     }
   }
 
@@ -115,10 +142,22 @@ impl File {
       data,
       description: None,
       duration_secs: None,
-      waveform: None
+      waveform: None,
+      content_type: None
     })
   }
 
+  /// Set the media type of the file
+  /// ```
+  /// # use slashook::structs::utils::File;
+  /// let file = File::new("data.json", "{}").set_content_type("application/json");
+  /// assert_eq!(file.content_type.as_deref(), Some("application/json"));
+  /// ```
+  pub fn set_content_type<T: ToString>(mut self, content_type: T) -> Self {
+    self.content_type = Some(content_type.to_string());
+    self
+  }
+
   /// Set a description for a file
   /// ```no_run
   /// # use slashook::structs::utils::File;
@@ -0,0 +1,39 @@
+// Copyright 2024 slashook Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use ring::signature;
+
+/// Verifies that a request body was signed by Discord
+///
+/// `signature` and `timestamp` are the values of the `X-Signature-Ed25519` and `X-Signature-Timestamp` headers
+/// and `public_key` is the public key of your application.\
+/// Use this to check requests yourself when handling interactions in your own web server.
+/// ```
+/// # use slashook::verify_signature;
+/// # use ring::{rand::SystemRandom, signature::{Ed25519KeyPair, KeyPair}};
+/// # let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+/// # let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+/// # let public_key = hex::encode(key_pair.public_key());
+/// # let signature = hex::encode(key_pair.sign(b"1700000000{\"type\":1}"));
+/// let body = b"{\"type\":1}";
+/// assert!(verify_signature(&public_key, &signature, "1700000000", body));
+/// assert!(!verify_signature(&public_key, &signature, "1700000001", body));
+/// assert!(!verify_signature(&public_key, "not hex", "1700000000", body));
+/// ```
+pub fn verify_signature<S: AsRef<[u8]>, T: AsRef<[u8]>>(public_key: &str, signature: S, timestamp: T, body: &[u8]) -> bool {
+  let decoding_pubkey = hex::decode(public_key);
+  let decoding_signature = hex::decode(signature);
+  if decoding_pubkey.is_err() || decoding_signature.is_err() { return false }
+
+  let decoded_pubkey: &[u8] = &decoding_pubkey.unwrap();
+  let decoded_signature: &[u8] = &decoding_signature.unwrap();
+
+  let usable_pubkey = signature::UnparsedPublicKey::new(&signature::ED25519, decoded_pubkey);
+  let message: &[u8] = &[timestamp.as_ref(), body].concat();
+
+  usable_pubkey.verify(message, decoded_signature).is_ok()
+}
@@ -8,12 +8,19 @@
 extern crate ring;
 extern crate hex;
 mod signature_headers;
+mod verified_body;
 mod multipart;
 
-use super::{Config, commands::handler::RocketCommand};
-use super::structs::interactions::{Interaction, InteractionType, InteractionCallback, InteractionCallbackType};
-use signature_headers::SignatureHeaders;
+use super::{Config, ErrorResponder, ErrorResponse, WebhookError, commands::handler::RocketCommand};
+use super::structs::{
+  interactions::{Interaction, InteractionType, ApplicationCommandType, InteractionCallback, InteractionCallbackType},
+  components::ComponentType,
+  utils::ValidationError
+};
+use verified_body::VerifiedBody;
 use rocket::{
+  Build,
+  Rocket,
   http::Status,
   request::Request,
   response::{self, Response, Responder, content},
@@ -21,7 +28,6 @@ use rocket::{
   tokio::sync::{mpsc, oneshot}
 };
 use serde_json::{Value, json};
-use ring::signature;
 
 enum Res {
   Raw {
@@ -31,9 +37,12 @@ enum Res {
   Response {
     status: Status,
     data: Box<InteractionCallback>
-  }
+  },
+  Error(WebhookError)
 }
 
+struct ErrorHandler(ErrorResponder);
+
 impl<'r> Responder<'r, 'static> for Res {
   fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
     let mut response = Response::build();
@@ -46,51 +55,71 @@ impl<'r> Responder<'r, 'static> for Res {
       },
 
       Self::Response{ status, data } => {
-        if data.data.as_ref().map_or(false, |d| d.files.is_some()) {
+        if data.data.as_ref().is_some_and(|d| d.files.is_some()) {
           response.merge(multipart::handle_multipart(*data)?);
         } else {
           let json = serde_json::to_string(&data).map_err(|_| Status::InternalServerError)?;
           response.merge(content::RawJson(json).respond_to(req)?);
         }
         response.status(status);
+      },
+
+      Self::Error(error) => {
+        let ErrorResponse{ status, body } = match req.rocket().state::<ErrorHandler>() {
+          Some(handler) => (handler.0)(error),
+          None => error.default_response()
+        };
+        if let Some(body) = body {
+          response.merge(content::RawJson(body.to_string()).respond_to(req)?);
+        }
+        response.status(Status::new(status));
       }
     }
 
-    response
-      .raw_header("User-Agent", crate::USER_AGENT)
-      .ok()
+    if let Some(ident) = req.rocket().state::<Config>().and_then(|c| c.server_ident.clone()) {
+      response.raw_header("User-Agent", ident);
+    }
+
+    response.ok()
   }
 }
 
-fn verify_signature(body: &[u8], headers: SignatureHeaders, public_key: &str) -> bool {
-  let decoding_pubkey = hex::decode(public_key);
-  let decoding_signature = hex::decode(headers.signature);
-  if decoding_pubkey.is_err() || decoding_signature.is_err() { return false }
+fn warn_unknown_values(interaction: &Interaction, body: &[u8]) {
+  let mut unknown = Vec::new();
 
-  let decoded_pubkey: &[u8] = &decoding_pubkey.unwrap();
-  let decoded_signature: &[u8] = &decoding_signature.unwrap();
+  if matches!(interaction.interaction_type, InteractionType::UNKNOWN) {
+    unknown.push(("interaction type", "/type"));
+  }
+  if let Some(data) = &interaction.data {
+    if matches!(data.command_type, Some(ApplicationCommandType::UNKNOWN)) {
+      unknown.push(("application command type", "/data/type"));
+    }
+    if matches!(data.component_type, Some(ComponentType::UNKNOWN)) {
+      unknown.push(("component type", "/data/component_type"));
+    }
+  }
 
-  let usable_pubkey = signature::UnparsedPublicKey::new(&signature::ED25519, decoded_pubkey);
-  let message: &[u8] = &[headers.timestamp, body].concat();
+  if unknown.is_empty() { return }
 
-  usable_pubkey.verify(message, decoded_signature).is_ok()
+  let raw: Value = serde_json::from_slice(body).unwrap_or_default();
+  for (name, pointer) in unknown {
+    let value = raw.pointer(pointer).cloned().unwrap_or_default();
+    eprintln!("Received an unknown {} from Discord: {}. A newer version of slashook may be required to handle it.", name, value);
+  }
 }
 
 #[post("/", data = "<body>")]
-async fn index(body: &[u8], headers: SignatureHeaders<'_>, config: &State<Config>, cmd_sender: &State<mpsc::UnboundedSender::<RocketCommand>>) -> Res {
-
-  if !verify_signature(body, headers, &config.public_key) {
-    return Res::Raw{ status: Status::Unauthorized, json: json!({ "error": "Bad signature" })}
-  }
-
-  let interaction: Interaction = match serde_json::from_slice(body) {
+async fn index(body: VerifiedBody<'_>, config: &State<Config>, cmd_sender: &State<mpsc::UnboundedSender::<RocketCommand>>) -> Res {
+  let interaction: Interaction = match serde_json::from_slice(body.0) {
     Ok(i) => i,
     Err(err) => {
       eprintln!("Received bad request body from Discord. Error: {}", err);
-      return Res::Raw{ status: Status::BadRequest, json: json!({ "error": "Bad body" })}
+      return Res::Error(WebhookError::BadBody)
     }
   };
 
+  warn_unknown_values(&interaction, body.0);
+
   match interaction.interaction_type {
     InteractionType::PING => {
       let response = InteractionCallback{
@@ -101,18 +130,32 @@ async fn index(body: &[u8], headers: SignatureHeaders<'_>, config: &State<Config
     },
 
     InteractionType::UNKNOWN => {
-      Res::Raw{ status: Status::NotFound, json: json!({ "error": "Unknown interaction type" }) }
+      Res::Error(WebhookError::UnknownInteractionType)
     },
 
     _ => {
       let (handler_send, handler_respond) = oneshot::channel::<anyhow::Result<InteractionCallback>>();
-      cmd_sender.send(RocketCommand(interaction, config.bot_token.clone(), handler_send)).expect("Cannot execute handler");
-      let response = handler_respond.await.unwrap();
+      let rest = config.rest_for_interaction.as_ref().map(|rest_for_interaction| rest_for_interaction(&interaction));
+      if cmd_sender.send(RocketCommand(interaction, config.bot_token.clone(), rest, handler_send)).is_err() {
+        eprintln!("Command handler is not running, cannot handle interaction");
+        return Res::Error(WebhookError::HandlerUnavailable)
+      }
+
+      let response = match handler_respond.await {
+        Ok(response) => response,
+        Err(_) => {
+          eprintln!("Command handler stopped before responding to the interaction");
+          return Res::Error(WebhookError::HandlerUnavailable)
+        }
+      };
 
       match response {
         Err(err) => {
           eprintln!("Error when processing command: {:?}", err);
-          Res::Raw{ status: Status::InternalServerError, json: json!({ "error": "Handler failed" }) }
+          if err.is::<ValidationError>() {
+            return Res::Error(WebhookError::InvalidResponse)
+          }
+          Res::Error(WebhookError::HandlerFailed)
         },
         Ok(res) => Res::Response{ status: Status::Ok, data: Box::new(res) }
       }
@@ -120,32 +163,130 @@ async fn index(body: &[u8], headers: SignatureHeaders<'_>, config: &State<Config
   }
 }
 
+#[catch(401)]
+fn unauthorized() -> Res {
+  Res::Error(WebhookError::BadSignature)
+}
+
+#[catch(415)]
+fn unsupported_encoding() -> Res {
+  Res::Error(WebhookError::UnsupportedEncoding)
+}
+
 #[catch(404)]
 fn not_found() -> Res {
-  Res::Raw{ status: Status::NotFound, json: json!({ "error": "Not found" }) }
+  Res::Error(WebhookError::NotFound)
 }
 
 #[catch(default)]
 fn default_error() -> Res {
-  Res::Raw{ status: Status::InternalServerError, json: json!({ "error": "Unexpected error" }) }
+  Res::Error(WebhookError::Unexpected)
 }
 
-pub(crate) async fn start(config: Config, sender: mpsc::UnboundedSender::<RocketCommand>) {
-  let figment = rocket::Config::figment()
+fn build(config: Config, sender: mpsc::UnboundedSender::<RocketCommand>, error_responder: Option<ErrorResponder>) -> Rocket<Build> {
+  let mut figment = rocket::Config::figment()
     .merge(("address", config.ip))
     .merge(("port", config.port))
-    .merge(("ident", crate::USER_AGENT))
     .merge(("log_level", rocket::config::LogLevel::Off));
 
-  let result = rocket::custom(figment)
+  if let Some(workers) = config.workers {
+    figment = figment.merge(("workers", workers));
+  }
+  if let Some(max_blocking) = config.max_blocking {
+    figment = figment.merge(("max_blocking", max_blocking));
+  }
+
+  figment = match &config.server_ident {
+    Some(ident) => figment.merge(("ident", ident)),
+    None => figment.merge(("ident", false))
+  };
+
+  let mut rocket = rocket::custom(figment)
     .mount("/", routes![index])
-    .register("/", catchers![not_found, default_error])
+    .register("/", catchers![unauthorized, unsupported_encoding, not_found, default_error])
     .manage(config)
-    .manage(sender)
-    .launch()
-    .await;
+    .manage(sender);
+
+  if let Some(error_responder) = error_responder {
+    rocket = rocket.manage(ErrorHandler(error_responder));
+  }
+
+  rocket
+}
+
+pub(crate) async fn start(config: Config, sender: mpsc::UnboundedSender::<RocketCommand>, error_responder: Option<ErrorResponder>) {
+  let result = build(config, sender, error_responder).launch().await;
 
   if let Err(error) = result {
     panic!("Couldn't start web server: {}", error);
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rocket::{http::{ContentType, Header}, local::asynchronous::Client};
+  use ring::{rand::SystemRandom, signature::{Ed25519KeyPair, KeyPair}};
+
+  const PING: &str = r#"{"id":"1","application_id":"2","type":1,"token":"token","version":1,"app_permissions":"0","entitlements":[]}"#;
+
+  async fn client() -> (Client, Ed25519KeyPair) {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+    let config = Config {
+      public_key: hex::encode(key_pair.public_key()),
+      ..Default::default()
+    };
+    let (sender, _) = mpsc::unbounded_channel::<RocketCommand>();
+    (Client::untracked(build(config, sender, None)).await.unwrap(), key_pair)
+  }
+
+  #[rocket::async_test]
+  async fn missing_headers_are_unauthorized() {
+    let (client, _) = client().await;
+    let res = client.post("/").header(ContentType::JSON).body(PING).dispatch().await;
+    assert_eq!(res.status(), Status::Unauthorized);
+  }
+
+  #[rocket::async_test]
+  async fn bad_signature_is_unauthorized() {
+    let (client, key_pair) = client().await;
+    let signature = hex::encode(key_pair.sign(format!("1700000000{}", PING).as_bytes()));
+    let res = client.post("/")
+      .header(ContentType::JSON)
+      .header(Header::new("X-Signature-Ed25519", signature))
+      .header(Header::new("X-Signature-Timestamp", "1700000001"))
+      .body(PING)
+      .dispatch().await;
+    assert_eq!(res.status(), Status::Unauthorized);
+  }
+
+  #[rocket::async_test]
+  async fn forged_body_is_unauthorized() {
+    let (client, key_pair) = client().await;
+    let signature = hex::encode(key_pair.sign(format!("1700000000{}", PING).as_bytes()));
+    let forged = PING.replace(r#""type":1"#, r#""type":2"#);
+    let res = client.post("/")
+      .header(ContentType::JSON)
+      .header(Header::new("X-Signature-Ed25519", signature))
+      .header(Header::new("X-Signature-Timestamp", "1700000000"))
+      .body(forged)
+      .dispatch().await;
+    assert_eq!(res.status(), Status::Unauthorized);
+  }
+
+  #[rocket::async_test]
+  async fn valid_signature_is_accepted() {
+    let (client, key_pair) = client().await;
+    let signature = hex::encode(key_pair.sign(format!("1700000000{}", PING).as_bytes()));
+    let res = client.post("/")
+      .header(ContentType::JSON)
+      .header(Header::new("X-Signature-Ed25519", signature))
+      .header(Header::new("X-Signature-Timestamp", "1700000000"))
+      .body(PING)
+      .dispatch().await;
+    assert_eq!(res.status(), Status::Ok);
+    let body: Value = serde_json::from_str(&res.into_string().await.unwrap()).unwrap();
+    assert_eq!(body["type"], 1);
+  }
+}
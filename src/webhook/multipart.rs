@@ -17,6 +17,7 @@ use rocket::{
 use common_multipart_rfc7578::client::multipart::{
   Body, Form, BoundaryGenerator
 };
+use mime::Mime;
 use tokio_util::io::StreamReader;
 use reqwest::multipart::Form as ReqwestForm;
 
@@ -32,14 +33,17 @@ pub fn handle_multipart(mut callback: InteractionCallback) -> response::Result<'
 
   let mut data = callback.data.unwrap();
   let files = data.files.take().unwrap();
-  let mut attachments = data.take_attachments();
+  let attachments = Attachment::for_upload(data.take_attachments(), &files);
+  data.set_attachments(attachments);
 
   for (i, file) in files.into_iter().enumerate() {
-    attachments.push(Attachment::from_file(i.to_string(), &file));
-    form.add_reader_file(format!("files[{}]", i), Cursor::new(file.data), file.filename);
+    let name = format!("files[{}]", i);
+    match file.content_type.as_deref().and_then(|c| c.parse::<Mime>().ok()) {
+      Some(mime) => form.add_reader_file_with_mime(name, Cursor::new(file.data), file.filename, mime),
+      None => form.add_reader_file(name, Cursor::new(file.data), file.filename)
+    }
   }
 
-  data.set_attachments(attachments);
   callback.data = Some(data);
   form.add_text("payload_json", serde_json::to_string(&callback).map_err(|_| Status::InternalServerError)?);
   let content_type = form.content_type();
@@ -51,3 +55,33 @@ pub fn handle_multipart(mut callback: InteractionCallback) -> response::Result<'
     .streamed_body(stream)
     .ok()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    commands::{MessageResponse, responder::CommandResponse},
+    structs::utils::File
+  };
+  use serde_json::Value;
+
+  #[rocket::async_test]
+  async fn send_message_with_file() {
+    let msg = MessageResponse::from("Here's a file").add_file(File::new("a.txt", "hello"));
+    let callback = InteractionCallback::from(CommandResponse::SendMessage(msg));
+    let mut response = handle_multipart(callback).unwrap();
+    let body = response.body_mut().to_string().await.unwrap();
+
+    let file_part = body.split_once("name=\"files[0]\"; filename=\"a.txt\"").expect("files[0] part is missing").1;
+    assert!(file_part.contains("hello"));
+
+    let payload = body.split_once("name=\"payload_json\"").expect("payload_json part is missing").1;
+    let payload = payload.split_once("\r\n\r\n").unwrap().1.split_once("\r\n--").unwrap().0;
+    let payload: Value = serde_json::from_str(payload).unwrap();
+    assert_eq!(payload["type"], 4);
+    let attachments = payload["data"]["attachments"].as_array().unwrap();
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0]["id"], "0");
+    assert_eq!(attachments[0]["filename"], "a.txt");
+  }
+}
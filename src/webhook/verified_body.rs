@@ -0,0 +1,88 @@
+// Copyright 2024 slashook Developers
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::signature_headers::SignatureHeaders;
+use crate::{Config, verify_signature};
+use rocket::{
+  http::Status,
+  request::{self, Request, FromRequest},
+  data::{Data, FromData, Outcome, Limits}
+};
+use std::io::Read;
+use flate2::read::{GzDecoder, ZlibDecoder};
+
+/// A request body that has been verified to be signed by Discord.
+///
+/// Routes receiving data from Discord should take this as their data guard instead of the raw body
+/// so that the signature check cannot be skipped. Failure results in a 401.
+pub struct VerifiedBody<'r>(pub &'r [u8]);
+
+#[derive(Debug)]
+pub enum VerifiedBodyError {
+  MissingHeaders,
+  MissingConfig,
+  BadBody,
+  UnsupportedEncoding,
+  BadSignature
+}
+
+struct DecompressedBody(Option<Vec<u8>>);
+
+fn decompress(encoding: &str, body: &[u8], limit: u64) -> Option<Vec<u8>> {
+  let reader: Box<dyn Read + '_> = match encoding.trim().to_ascii_lowercase().as_str() {
+    "gzip" | "x-gzip" => Box::new(GzDecoder::new(body)),
+    "deflate" => Box::new(ZlibDecoder::new(body)),
+    "br" => Box::new(brotli_decompressor::Decompressor::new(body, 4096)),
+    _ => return None
+  };
+
+  let mut decompressed = Vec::new();
+  reader.take(limit + 1).read_to_end(&mut decompressed).ok()?;
+  if decompressed.len() as u64 > limit { return None }
+  Some(decompressed)
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for VerifiedBody<'r> {
+  type Error = VerifiedBodyError;
+
+  async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+    let headers = match SignatureHeaders::from_request(req).await {
+      request::Outcome::Success(headers) => headers,
+      _ => return Outcome::Error((Status::Unauthorized, VerifiedBodyError::MissingHeaders))
+    };
+
+    let config = match req.rocket().state::<Config>() {
+      Some(config) => config,
+      None => return Outcome::Error((Status::InternalServerError, VerifiedBodyError::MissingConfig))
+    };
+
+    let body = match <&'r [u8]>::from_data(req, data).await {
+      Outcome::Success(body) => body,
+      Outcome::Forward(forward) => return Outcome::Forward(forward),
+      Outcome::Error((status, _)) => return Outcome::Error((status, VerifiedBodyError::BadBody))
+    };
+
+    let encoding = req.headers().get_one("Content-Encoding").filter(|e| !e.eq_ignore_ascii_case("identity"));
+    let body: &'r [u8] = match encoding {
+      Some(encoding) if config.decompress_requests => {
+        let limit = req.limits().get("bytes").unwrap_or(Limits::BYTES).as_u64();
+        match &req.local_cache(|| DecompressedBody(decompress(encoding, body, limit))).0 {
+          Some(decompressed) => decompressed,
+          None => return Outcome::Error((Status::UnsupportedMediaType, VerifiedBodyError::UnsupportedEncoding))
+        }
+      },
+      _ => body
+    };
+
+    if !verify_signature(&config.public_key, headers.signature, headers.timestamp, body) {
+      return Outcome::Error((Status::Unauthorized, VerifiedBodyError::BadSignature))
+    }
+
+    Outcome::Success(VerifiedBody(body))
+  }
+}